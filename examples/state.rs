@@ -13,7 +13,6 @@ struct State {
     allowed_values: Vec<String>,
 }
 
-#[async_trait::async_trait]
 impl axum_typed_multipart::TryFromFieldWithState<State> for ValidatedField {
     async fn try_from_field_with_state(
         mut field: axum::extract::multipart::Field<'_>,
@@ -2,7 +2,7 @@ use axum::extract::DefaultBodyLimit;
 use axum::http::StatusCode;
 use axum::routing::post;
 use axum::Router;
-use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
+use axum_typed_multipart::{FieldData, SafeFileName, TryFromMultipart, TypedMultipart};
 use tempfile_3::NamedTempFile;
 
 #[derive(TryFromMultipart)]
@@ -21,8 +21,13 @@ async fn upload_asset(
     TypedMultipart(UploadAssetRequest { image, author }): TypedMultipart<UploadAssetRequest>,
 ) -> StatusCode {
     let dir = tempfile_3::tempdir().unwrap();
-    let file_name = image.metadata.file_name.unwrap_or(String::from("data.bin"));
-    let path = dir.path().join(format!("{author}_{file_name}"));
+    // The file name and author are supplied by the client, so they are sanitized before
+    // being joined to the directory.
+    let file_name = image.metadata.safe_file_name().map_or(String::from("data.bin"), String::from);
+    let Some(file_name) = SafeFileName::new(&format!("{author}_{file_name}")) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let path = dir.path().join(file_name);
 
     match image.contents.persist(path) {
         Ok(_) => StatusCode::CREATED,
@@ -1,9 +1,8 @@
-use axum::body::Bytes;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::{Json, Router};
-use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
 use serde::Serialize;
 use utoipa::{OpenApi, ToSchema};
 use utoipa_rapidoc::RapiDoc;
@@ -18,10 +17,10 @@ pub struct FileUpload {
     #[schema(example = "John Doe")]
     name: String,
 
-    /// File or files to upload
+    /// File to upload
     #[form_data(limit = "2MiB")]
-    #[schema(value_type = Vec<u8>)]
-    file: Vec<FieldData<Bytes>>,
+    #[schema(content_media_type = "application/octet-stream")]
+    file: Vec<u8>,
 }
 
 #[derive(Debug, Default, Serialize, ToSchema)]
@@ -50,9 +49,7 @@ async fn file_upload(
     TypedMultipart(FileUpload { name, file }): TypedMultipart<FileUpload>,
 ) -> Response {
     println!("User's name: {name}");
-    for f in file.into_iter() {
-        println!("Filename: {:?}", f.metadata.file_name);
-    }
+    println!("File size: {} bytes", file.len());
     (StatusCode::OK, Json(Status { status: "ok".into(), error: None })).into_response()
 }
 
@@ -0,0 +1,57 @@
+use quote::{quote, ToTokens};
+
+/// Allowed media type of a field, parsed from the `content_type = "..."` attribute.
+///
+/// Accepts exact media types (e.g. `image/png`) and wildcards (e.g. `image/*` or `*/*`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType(pub String);
+
+impl darling::FromMeta for ContentType {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let valid = match value.split_once('/') {
+            Some(("*", subtype)) => subtype == "*",
+            Some((kind, subtype)) => {
+                let is_token = |s: &str| {
+                    !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || "/;*".contains(c))
+                };
+                is_token(kind) && (subtype == "*" || is_token(subtype))
+            }
+            None => false,
+        };
+
+        if valid {
+            Ok(Self(value.to_ascii_lowercase()))
+        } else {
+            Err(darling::Error::custom("must be a media type (e.g., \"image/png\", \"image/*\")"))
+        }
+    }
+}
+
+impl ToTokens for ContentType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let value = &self.0;
+        tokens.extend(quote! { #value });
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use darling::FromMeta;
+
+    #[test]
+    fn test_valid_content_types() {
+        assert_eq!(ContentType::from_string("image/png").unwrap().0, "image/png");
+        assert_eq!(ContentType::from_string("Image/*").unwrap().0, "image/*");
+        assert_eq!(ContentType::from_string("*/*").unwrap().0, "*/*");
+    }
+
+    #[test]
+    fn test_invalid_content_types() {
+        for value in ["image", "*/png", "image/", "image/png; charset=utf-8", "image/p*"] {
+            let err = ContentType::from_string(value).unwrap_err();
+            assert!(err.to_string().contains("must be a media type"));
+        }
+    }
+}
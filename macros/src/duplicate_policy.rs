@@ -0,0 +1,44 @@
+/// Behavior when a single valued field is present multiple times in the request, parsed from the
+/// `duplicates = "..."` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Keep the first occurrence, ignoring the following ones.
+    First,
+
+    /// Keep the last occurrence, overwriting the previous ones.
+    #[default]
+    Last,
+
+    /// Return a `DuplicateField` error.
+    Error,
+}
+
+impl darling::FromMeta for DuplicatePolicy {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "first" => Ok(Self::First),
+            "last" => Ok(Self::Last),
+            "error" => Ok(Self::Error),
+            _ => Err(darling::Error::unknown_value_with_alts(value, &["first", "last", "error"])),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use darling::FromMeta;
+
+    #[test]
+    fn test_valid_policies() {
+        assert_eq!(DuplicatePolicy::from_string("first").unwrap(), DuplicatePolicy::First);
+        assert_eq!(DuplicatePolicy::from_string("last").unwrap(), DuplicatePolicy::Last);
+        assert_eq!(DuplicatePolicy::from_string("error").unwrap(), DuplicatePolicy::Error);
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        assert!(DuplicatePolicy::from_string("ignore").is_err());
+    }
+}
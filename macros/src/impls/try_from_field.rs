@@ -42,6 +42,9 @@ struct TryFromFieldInputData {
 }
 
 /// Derive `TryFromField` for arbitrary unit-enums.
+///
+/// The generated code implements `TryFromChunks`, which provides the `TryFromField`
+/// implementation with size limit handling.
 pub fn macro_impl(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     let TryFromFieldInputData { ident, data, rename_all } =
@@ -61,14 +64,15 @@ pub fn macro_impl(input: TokenStream) -> TokenStream {
     });
 
     let res = quote! {
-        #[::axum_typed_multipart::async_trait]
-        impl ::axum_typed_multipart::TryFromField for #ident {
-            async fn try_from_field(
-                field: ::axum::extract::multipart::Field<'_>,
-                limit_bytes: ::core::option::Option<usize>,
+        impl ::axum_typed_multipart::TryFromChunks for #ident {
+            async fn try_from_chunks(
+                chunks: impl ::axum_typed_multipart::__private::Stream<
+                    Item = ::core::result::Result<::axum::body::Bytes, ::axum_typed_multipart::TypedMultipartError>
+                > + ::core::marker::Send,
+                metadata: ::axum_typed_multipart::FieldMetadata,
             ) -> ::core::result::Result<Self, ::axum_typed_multipart::TypedMultipartError> {
-                let field_name = <::std::string::String as ::core::convert::From<&str>>::from(field.name().unwrap_or_default());
-                let value: ::std::string::String = ::axum_typed_multipart::TryFromField::try_from_field(field, limit_bytes).await?;
+                let field_name = ::core::clone::Clone::clone(&metadata.name).unwrap_or_default();
+                let value = <::std::string::String as ::axum_typed_multipart::TryFromChunks>::try_from_chunks(chunks, metadata).await?;
                 match value.as_str() {
                     #(#match_arms),*,
                     _ => ::core::result::Result::Err(::axum_typed_multipart::TypedMultipartError::InvalidEnumValue {
@@ -1,12 +1,15 @@
 use crate::case_conversion::RenameCase;
+use crate::content_type::ContentType;
+use crate::duplicate_policy::DuplicatePolicy;
 use crate::limit_bytes::LimitBytes;
-use crate::util::{matches_option_signature, matches_vec_signature, strip_leading_rawlit};
+use crate::util::strip_leading_rawlit;
 use darling::{FromDeriveInput, FromField};
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro_error2::abort;
+use quote::{format_ident, quote};
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(try_from_multipart), supports(struct_named))]
+#[darling(attributes(try_from_multipart), supports(struct_named, struct_newtype, struct_tuple))]
 struct InputData {
     ident: syn::Ident,
 
@@ -15,9 +18,24 @@ struct InputData {
     #[darling(default)]
     strict: bool,
 
+    #[darling(default)]
+    deny_unknown_fields: bool,
+
+    #[darling(default)]
+    deny_duplicates: bool,
+
+    #[darling(default)]
+    deny_nameless: bool,
+
+    #[darling(default)]
+    duplicates: Option<DuplicatePolicy>,
+
     #[darling(default)]
     rename_all: Option<RenameCase>,
 
+    #[darling(default)]
+    positional: bool,
+
     #[darling(default)]
     state: Option<syn::Path>,
 }
@@ -34,8 +52,32 @@ struct FieldData {
     #[darling(default)]
     limit: LimitBytes,
 
+    #[darling(multiple)]
+    content_type: Vec<ContentType>,
+
+    #[darling(default)]
+    sniff: bool,
+
     #[darling(default)]
     default: bool,
+
+    #[darling(default)]
+    checkbox: bool,
+
+    #[darling(default)]
+    trim: bool,
+
+    #[darling(default)]
+    lowercase: bool,
+
+    #[darling(default)]
+    nfc: bool,
+
+    #[darling(default)]
+    delimiter: Option<String>,
+
+    #[darling(default)]
+    rest: bool,
 }
 
 impl FieldData {
@@ -55,129 +97,378 @@ impl FieldData {
             field_in_struct
         }
     }
+
+    /// Whether the textual representation of the field must be transformed before parsing.
+    fn is_normalized(&self) -> bool {
+        self.trim || self.lowercase || self.nfc
+    }
+
+    /// Generate the `Normalization` value passed to the parsing helpers.
+    fn normalization(&self) -> proc_macro2::TokenStream {
+        let Self { trim, lowercase, nfc, .. } = self;
+        quote! {
+            ::axum_typed_multipart::__private::Normalization {
+                trim: #trim,
+                lowercase: #lowercase,
+                nfc: #nfc,
+            }
+        }
+    }
+
+    /// Generate the expression selecting how the occurrences of the field are combined, see
+    /// `axum_typed_multipart::__private::Dispatch`.
+    fn dispatch(&self) -> proc_macro2::TokenStream {
+        let ty = &self.ty;
+        if self.rest || self.delimiter.is_some() {
+            // These parameters only make sense for fields collecting multiple values, so
+            // `FieldCollector` takes precedence over byte payloads like `Vec<u8>`.
+            return quote! {
+                (&::axum_typed_multipart::__private::Dispatch::<#ty>(::core::marker::PhantomData)).collector()
+            };
+        }
+        quote! {
+            (&&::axum_typed_multipart::__private::Dispatch::<#ty>(::core::marker::PhantomData)).collector()
+        }
+    }
+
+    /// Name of the variable holding the result of [FieldData::dispatch].
+    fn collector(&self) -> syn::Ident {
+        format_ident!("__{}_collector__", self.ident.as_ref().unwrap())
+    }
+
+    /// Name of the variable holding the number of occurrences of the field.
+    fn count(&self) -> syn::Ident {
+        format_ident!("__{}_count__", self.ident.as_ref().unwrap())
+    }
+
+    /// Generate the patterns the `Content-Type` of the field is checked against, see the
+    /// parsing helpers in `axum_typed_multipart::__private`.
+    fn content_type(&self) -> proc_macro2::TokenStream {
+        let content_type = &self.content_type;
+        quote! { &[#(#content_type),*] }
+    }
+
+    /// Generate the expression parsing the current field into a single value.
+    fn value(&self) -> proc_macro2::TokenStream {
+        let limit = &self.limit;
+        let content_type = self.content_type();
+        if self.sniff {
+            let rule = if self.content_type.is_empty() {
+                quote! { ::axum_typed_multipart::__private::SniffRule::AgreeWithDeclared }
+            } else {
+                quote! { ::axum_typed_multipart::__private::SniffRule::Allow(#content_type) }
+            };
+            quote! {
+                ::axum_typed_multipart::__private::sniffed(__field__, #limit, #content_type, #rule).await?
+            }
+        } else if self.is_normalized() {
+            let normalization = self.normalization();
+            quote! {
+                ::axum_typed_multipart::__private::normalized(__field__, #limit, #content_type, #normalization, __errors__).await?
+            }
+        } else {
+            quote! {
+                ::axum_typed_multipart::__private::parse(__field__, #limit, #content_type, state, __errors__).await?
+            }
+        }
+    }
 }
 
 /// Derive the `TryFromMultipart` trait for arbitrary named structs.
 pub fn macro_impl(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-    let InputData { ident, data, strict, rename_all, state } =
-        match InputData::from_derive_input(&input) {
-            Ok(input) => input,
-            Err(err) => return err.write_errors().into(),
-        };
+    let InputData {
+        ident,
+        data,
+        strict,
+        deny_unknown_fields,
+        deny_duplicates,
+        deny_nameless,
+        duplicates,
+        rename_all,
+        positional,
+        state,
+    } = match InputData::from_derive_input(&input) {
+        Ok(input) => input,
+        Err(err) => return err.write_errors().into(),
+    };
 
-    let fields = data.take_struct().unwrap();
+    // `strict` is a shorthand for all the `deny_*` options.
+    let deny_unknown_fields = strict || deny_unknown_fields;
+    let deny_nameless = strict || deny_nameless;
+    let duplicates = match duplicates {
+        Some(DuplicatePolicy::Error) | None if strict || deny_duplicates => DuplicatePolicy::Error,
+        Some(_) if strict || deny_duplicates => {
+            abort!(ident, "`duplicates` conflicts with `strict` and `deny_duplicates`")
+        }
+        Some(duplicates) => duplicates,
+        None => DuplicatePolicy::default(),
+    };
 
-    let declarations = fields.iter().map(|FieldData { ident, ty, .. }| {
-        if matches_vec_signature(ty) {
-            quote! { let mut #ident: #ty = ::std::vec::Vec::new(); }
-        } else if matches_option_signature(ty) {
-            quote! { let mut #ident: #ty = ::core::option::Option::None; }
-        } else {
-            quote! { let mut #ident: ::core::option::Option<#ty> = ::core::option::Option::None; }
+    let mut fields = data.take_struct().unwrap();
+    let is_tuple = fields.is_tuple();
+
+    // Fields of tuple structs are bound to generated variables and must be named explicitly,
+    // or after their position when `positional` is set.
+    if is_tuple {
+        for (index, field) in fields.fields.iter_mut().enumerate() {
+            if field.field_name.is_none() && !field.rest {
+                if !positional {
+                    abort!(
+                        field.ty,
+                        "tuple struct fields require a `field_name` attribute or `positional`"
+                    );
+                }
+                field.field_name = Some(index.to_string());
+            }
+            field.ident = Some(format_ident!("__field{}__", index));
+        }
+    } else if positional {
+        abort!(ident, "`positional` is only supported on tuple structs");
+    }
+
+    let mut rest_fields = fields.iter().filter(|FieldData { rest, .. }| *rest);
+    let rest_field = rest_fields.next();
+    if let Some(FieldData { ty, .. }) = rest_fields.next() {
+        abort!(ty, "only one field can be marked as `rest`");
+    }
+
+    for field @ FieldData { ty, checkbox, delimiter, rest, sniff, .. } in fields.iter() {
+        if *rest && (*checkbox || delimiter.is_some()) {
+            abort!(ty, "`rest` cannot be combined with `checkbox` or `delimiter`");
+        }
+        if *sniff && (*checkbox || delimiter.is_some() || field.is_normalized()) {
+            abort!(ty, "`sniff` cannot be combined with `checkbox`, `delimiter` or normalization");
+        }
+        if let Some(delimiter) = delimiter {
+            if delimiter.is_empty() {
+                abort!(ty, "`delimiter` must not be empty");
+            }
+            if *checkbox {
+                abort!(ty, "`delimiter` cannot be combined with `checkbox`");
+            }
+        }
+    }
+
+    // Whether a flag must be kept to detect repeated occurrences of single valued fields.
+    let track_duplicates = duplicates != DuplicatePolicy::Last;
+
+    let declarations = fields.iter().map(|field @ FieldData { ident, rest, .. }| {
+        let dispatch = field.dispatch();
+        let collector = field.collector();
+        let seen = (track_duplicates && !*rest).then(|| {
+            let seen = format_ident!("__{}_seen__", ident.as_ref().unwrap());
+            quote! { let mut #seen = false; }
+        });
+        let count = (!*rest).then(|| {
+            let count = field.count();
+            quote! { let mut #count: usize = 0; }
+        });
+
+        quote! {
+            let #collector = #dispatch;
+            let mut #ident = #collector.init();
+            #seen
+            #count
         }
     });
 
     let mut assignments = fields
         .iter()
-        .map(|field @ FieldData { ident, ty, limit, .. }| {
+        .filter(|FieldData { rest, .. }| !*rest)
+        .map(|field @ FieldData { ident, ty, limit, checkbox, delimiter, .. }| {
             let name = field.name(rename_all);
-            let value = quote! {
-                <_ as ::axum_typed_multipart::TryFromFieldWithState<_>>::try_from_field_with_state(__field__, #limit, state).await?
-            };
+            let normalization = field.normalization();
+            let value = field.value();
+            let content_type = field.content_type();
 
-            let assignment = if matches_vec_signature(ty) {
-                quote! { #ident.push(#value); }
-            } else if strict {
+            let collector = field.collector();
+            let count = field.count();
+            let push = if let Some(delimiter) = delimiter {
                 quote! {
-                    if #ident.is_none() {
-                        #ident = ::core::option::Option::Some(#value);
-                    } else {
+                    let __items__ = ::axum_typed_multipart::__private::delimited(
+                        __field__,
+                        #limit,
+                        #content_type,
+                        #normalization,
+                        #delimiter,
+                        #count,
+                        __errors__,
+                    ).await?;
+                    for __item__ in __items__ {
+                        if #collector.accepts(#count) {
+                            <#ty as ::axum_typed_multipart::FieldCollector>::push(&mut #ident, __item__);
+                        }
+                        #count += 1;
+                    }
+                }
+            } else if *checkbox {
+                quote! {
+                    if let ::core::option::Option::Some(__value__) =
+                        #collector.checkbox(__field__, #limit, #content_type, #normalization, __errors__).await?
+                    {
+                        if #collector.accepts(#count) {
+                            #collector.insert(&mut #ident, __key__, __value__);
+                        }
+                        #count += 1;
+                    }
+                }
+            } else {
+                quote! {
+                    if #collector.accepts(#count) {
+                        #collector.insert(&mut #ident, __key__, #value);
+                    }
+                    #count += 1;
+                }
+            };
+
+            let seen = format_ident!("__{}_seen__", ident.as_ref().unwrap());
+            let assignment = match duplicates {
+                DuplicatePolicy::First => quote! {
+                    if !#collector.is_duplicate(&#ident, &__key__, #seen) {
+                        #push
+                    }
+                    #seen = true;
+                },
+                DuplicatePolicy::Last => push,
+                DuplicatePolicy::Error => quote! {
+                    if #collector.is_duplicate(&#ident, &__key__, #seen) {
                         return ::core::result::Result::Err(
                             ::axum_typed_multipart::TypedMultipartError::DuplicateField {
-                                field_name: <::std::string::String as ::core::convert::From<&str>>::from(#name)
+                                field_name: <::std::string::String as ::core::convert::From<&str>>::from(__field_name__)
                             }
                         );
                     }
-                }
-            } else {
-                quote! { #ident = ::core::option::Option::Some(#value); }
+                    #push
+                    #seen = true;
+                },
             };
 
             quote! {
-                if __field_name__ == #name {
+                if let ::core::option::Option::Some(__key__) = #collector.key(__field_name__, #name) {
                     #assignment
                 }
             }
         })
         .collect::<Vec<_>>();
 
-    if strict {
+    if let Some(field @ FieldData { ident, .. }) = rest_field {
+        let value = field.value();
+        let collector = field.collector();
+
+        // The key must be copied from the name before the field is consumed by the parser.
+        assignments.push(quote! {
+            {
+                let __key__ = #collector.rest_key(__field_name__);
+                let __value__ = #value;
+                #collector.push_rest(&mut #ident, __key__, __value__);
+            }
+        })
+    } else if deny_unknown_fields {
         assignments.push(quote! {
             {
                 return ::core::result::Result::Err(
                     ::axum_typed_multipart::TypedMultipartError::UnknownField {
-                        field_name: __field_name__
+                        field_name: <::std::string::String as ::core::convert::From<&str>>::from(__field_name__)
                     }
                 );
             }
         })
     }
 
-    let required_fields = fields
-        .iter()
-        .filter(|FieldData { ty, .. }| !matches_option_signature(ty) && !matches_vec_signature(ty));
-    let default_fields = required_fields.clone().filter(|FieldData { default, .. }| *default);
-    let default_assignments = default_fields.map(|FieldData { ident, ty, .. }| {
-        quote! {
-            let #ident: ::core::option::Option<#ty> = #ident.or_else(|| ::core::option::Option::Some(<#ty as ::core::default::Default>::default()));
-        }
-    });
-
-    let checks = required_fields.map(|field @ FieldData { ident, .. }| {
-        let field_name = field.name(rename_all);
-        quote! {
-            let #ident = #ident.ok_or(
-                ::axum_typed_multipart::TypedMultipartError::MissingField {
-                    field_name: <::std::string::String as ::core::convert::From<&str>>::from(#field_name)
+    let checks =
+        fields.iter().map(|field @ FieldData { ident, ty, default, checkbox, rest, .. }| {
+            let collector = field.collector();
+            let field_name = field.name(rename_all);
+            let count_check = if *rest {
+                quote! {
+                    ::core::result::Result::<(), ::axum_typed_multipart::TypedMultipartError>::Ok(())
                 }
-            )?;
-        }
-    });
+            } else {
+                let count = field.count();
+                quote! { #collector.check_count(#field_name, #count) }
+            };
+            let fallback = if *default || *checkbox {
+                quote! { ::core::result::Result::Ok(<#ty as ::core::default::Default>::default()) }
+            } else {
+                quote! { #collector.missing(#field_name) }
+            };
+
+            // The value is `None` if the field is invalid and the errors are being collected.
+            quote! {
+                let #ident: ::core::option::Option<#ty> = __errors__.check(
+                    #count_check.and_then(|()| match #collector.finish(#ident) {
+                        ::core::option::Option::Some(__value__) => ::core::result::Result::Ok(__value__),
+                        ::core::option::Option::None => #fallback,
+                    })
+                )?;
+            }
+        });
 
-    let idents = fields.iter().map(|FieldData { ident, .. }| ident);
+    let idents = fields.iter().map(|FieldData { ident, .. }| ident).collect::<Vec<_>>();
+    let constructor = if is_tuple {
+        quote! { Self(#(#idents),*) }
+    } else {
+        quote! { Self { #(#idents),* } }
+    };
 
-    let missing_field_name_fallback = if strict {
+    let missing_field_name_fallback = if deny_nameless {
         quote! { return ::core::result::Result::Err(::axum_typed_multipart::TypedMultipartError::NamelessField) }
     } else {
-        quote! { continue }
+        quote! { return ::core::result::Result::Ok(()) }
     };
 
     let generic = state.is_none().then(|| quote! { <S: ::core::marker::Sync> });
     let state = state.map(|state| quote! { #state }).unwrap_or(quote! { S });
 
     let output = quote! {
-        #[::axum_typed_multipart::async_trait]
         impl #generic ::axum_typed_multipart::TryFromMultipartWithState<#state> for #ident {
             async fn try_from_multipart_with_state(multipart: &mut ::axum::extract::multipart::Multipart, state: &#state) -> ::core::result::Result<Self, ::axum_typed_multipart::TypedMultipartError> {
+                let mut __errors__ = <::axum_typed_multipart::__private::FieldErrors as ::core::default::Default>::default();
+                let __value__ = <Self as ::axum_typed_multipart::TryFromMultipartWithState<#state>>::try_from_multipart_with_state_and_errors(multipart, state, &mut __errors__).await?;
+                __errors__.into_result(__value__)
+            }
+
+            async fn try_from_multipart_with_state_and_errors(
+                multipart: &mut ::axum::extract::multipart::Multipart,
+                state: &#state,
+                __errors__: &mut ::axum_typed_multipart::__private::FieldErrors,
+            ) -> ::core::result::Result<::core::option::Option<Self>, ::axum_typed_multipart::TypedMultipartError> {
+                #[allow(unused_imports)]
+                use ::axum_typed_multipart::__private::{
+                    ViaBytes as _, ViaCollector as _, ViaMap as _, ViaOption as _, ViaResult as _,
+                    ViaSingle as _,
+                };
+
                 #(#declarations)*
 
                 while let ::core::option::Option::Some(__field__) = multipart.next_field().await? {
-                    let __field_name__ = match __field__.name() {
-                        | ::core::option::Option::Some("")
-                        | ::core::option::Option::None => #missing_field_name_fallback,
-                        | ::core::option::Option::Some(name) => <::std::string::String as ::core::convert::From<&str>>::from(name),
-                    };
-
-                    #(#assignments) else *
+                    // Errors raised while parsing the field are stored if they are being collected.
+                    let __res__: ::core::result::Result<(), ::axum_typed_multipart::TypedMultipartError> = async {
+                        // Borrowed from the field, the borrow ends before the field is parsed.
+                        let __field_name__ = match __field__.name() {
+                            | ::core::option::Option::Some("")
+                            | ::core::option::Option::None => #missing_field_name_fallback,
+                            | ::core::option::Option::Some(name) => name,
+                        };
+
+                        #(#assignments) else *
+                        ::core::result::Result::Ok(())
+                    }.await;
+                    if let ::core::result::Result::Err(__err__) = __res__ {
+                        __errors__.record(__err__)?;
+                    }
                 }
 
-                #(#default_assignments)*
-
                 #(#checks)*
 
-                ::core::result::Result::Ok(Self { #(#idents),* })
+                match (#(#idents,)*) {
+                    (#(::core::option::Option::Some(#idents),)*) if __errors__.is_empty() => {
+                        ::core::result::Result::Ok(::core::option::Option::Some(#constructor))
+                    }
+                    _ => ::core::result::Result::Ok(::core::option::Option::None),
+                }
             }
         }
     };
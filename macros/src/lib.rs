@@ -3,6 +3,8 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 mod case_conversion;
+mod content_type;
+mod duplicate_policy;
 mod impls;
 mod limit_bytes;
 mod util;
@@ -0,0 +1,98 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    answers: [u8; 3],
+    #[form_data(delimiter = ",")]
+    point: [i32; 2],
+}
+
+fn form(answers: &[&'static str]) -> Form {
+    answers
+        .iter()
+        .fold(Form::new().text("point", "1,-1"), |form, answer| form.text("answers", *answer))
+}
+
+#[tokio::test]
+async fn test_array() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.answers, [3, 1, 2]);
+        assert_eq!(data.point, [1, -1]);
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form(&["3", "1", "2"]))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_array_wrong_count() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let client = TestClient::new(Router::new().route("/", post(handler)));
+
+    for (answers, actual) in [(&["1", "2"][..], 2), (&[][..], 0), (&["1", "2", "3", "4"][..], 4)] {
+        let res = client.post("/").multipart(form(answers)).send().await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await.unwrap(),
+            format!("field 'answers' must be present 3 times, found {actual}")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_array_excess_not_parsed() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    // Occurrences past the expected count are counted but never parsed.
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form(&["1", "2", "3", "invalid"]))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'answers' must be present 3 times, found 4");
+}
+
+#[tokio::test]
+async fn test_array_delimiter_wrong_count() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let form = Form::new()
+        .text("answers", "1")
+        .text("answers", "2")
+        .text("answers", "3")
+        .text("point", "1,2,3");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'point' must be present 2 times, found 3");
+}
@@ -0,0 +1,117 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+use std::collections::{BTreeMap, HashMap};
+
+type Meta = HashMap<String, String>;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    items: Vec<String>,
+    meta: Meta,
+    sizes: std::collections::BTreeMap<String, u32>,
+}
+
+#[allow(dead_code)]
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct StrictData {
+    items: Vec<String>,
+    meta: BTreeMap<String, String>,
+}
+
+#[tokio::test]
+async fn test_brackets() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.items, vec!["a", "b", "c"]);
+        assert_eq!(data.meta.len(), 2);
+        assert_eq!(data.meta["color"], "blue");
+        assert_eq!(data.meta["shape"], "round");
+        assert_eq!(data.sizes, BTreeMap::new());
+    };
+
+    let form = Form::new()
+        .text("items[]", "a")
+        .text("items", "b")
+        .text("items[]", "c")
+        .text("meta[color]", "red")
+        .text("meta[shape]", "round")
+        .text("meta[color]", "blue")
+        .text("meta[]", "ignored")
+        .text("meta[a][b]", "ignored");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_brackets_invalid_value() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("sizes[small]", "x"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        res.text().await.unwrap(),
+        "field 'sizes[small]' must be of type 'u32': invalid digit found in string"
+    );
+}
+
+#[tokio::test]
+async fn test_brackets_strict_malformed() {
+    async fn handler(_: TypedMultipart<StrictData>) {
+        panic!("should not be called");
+    }
+
+    for (name, error) in [
+        ("meta[color", "field 'meta[color' is not expected"),
+        ("meta[]", "field 'meta[]' is not expected"),
+        ("meta[a][b]", "field 'meta[a][b]' is not expected"),
+        ("items[0]", "field 'items[0]' is not expected"),
+    ] {
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(Form::new().text(name, "data"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await.unwrap(), error);
+    }
+}
+
+#[tokio::test]
+async fn test_brackets_strict_duplicate_key() {
+    async fn handler(_: TypedMultipart<StrictData>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("meta[color]", "red").text("meta[color]", "blue"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'meta[color]' is already present");
+}
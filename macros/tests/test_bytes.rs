@@ -0,0 +1,72 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::{Form, Part};
+use std::sync::Arc;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    vec: Vec<u8>,
+    boxed: Box<[u8]>,
+    shared: Arc<[u8]>,
+    optional: Option<Vec<u8>>,
+    files: Vec<FieldData<Vec<u8>>>,
+    #[form_data(delimiter = ",")]
+    numbers: Vec<u8>,
+}
+
+#[tokio::test]
+async fn test_bytes() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.vec, [0x80, 0x81]);
+        assert_eq!(&*data.boxed, b"boxed");
+        assert_eq!(&*data.shared, b"shared");
+        assert_eq!(data.optional, None);
+        assert_eq!(data.files.len(), 2);
+        assert_eq!(data.files[0].contents, b"first");
+        assert_eq!(data.files[1].metadata.file_name.as_deref(), Some("second.txt"));
+        assert_eq!(data.numbers, [1, 2, 3]);
+    }
+
+    let form = Form::new()
+        .part("vec", Part::bytes(vec![0x80, 0x81]))
+        .text("boxed", "boxed")
+        .text("shared", "shared")
+        .text("files", "first")
+        .part("files", Part::text("second").file_name("second.txt"))
+        .text("numbers", "1,2")
+        .text("numbers", "3");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_bytes_duplicate() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.vec, b"second");
+    }
+
+    // `Vec<u8>` is a single value, so only the last occurrence is kept.
+    let form =
+        Form::new().text("vec", "first").text("vec", "second").text("boxed", "").text("shared", "");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
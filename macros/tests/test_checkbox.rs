@@ -0,0 +1,121 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromField, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(Debug, PartialEq, TryFromField)]
+#[try_from_field(rename_all = "snake_case")]
+enum Topping {
+    Cheese,
+    Ham,
+    Olives,
+}
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(checkbox)]
+    subscribe: bool,
+
+    #[form_data(checkbox)]
+    toppings: Vec<Topping>,
+}
+
+#[tokio::test]
+async fn test_checkbox_checked() {
+    for value in ["on", "true", "1", "yes", "custom", ""] {
+        let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+            assert!(data.subscribe);
+        };
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(Form::new().text("subscribe", value))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_checkbox_unchecked() {
+    for form in [
+        Form::new().text("unknown", "data"),
+        Form::new().text("subscribe", "off"),
+        Form::new().text("subscribe", "0"),
+    ] {
+        let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+            assert!(!data.subscribe);
+            assert_eq!(data.toppings, vec![]);
+        };
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(form)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_checkbox_hidden_input() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert!(data.subscribe);
+    };
+
+    // Frameworks like Rails render a hidden "0" input before the checkbox itself.
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("subscribe", "0").text("subscribe", "1"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_checkbox_group() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.toppings, vec![Topping::Cheese, Topping::Olives]);
+    };
+
+    let form = Form::new()
+        .text("toppings", "") // hidden input sent when no box is checked
+        .text("toppings", "cheese")
+        .text("toppings", "olives");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_checkbox_group_invalid() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("toppings", "pineapple"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "'pineapple' is not a valid value for field 'toppings'");
+}
@@ -0,0 +1,111 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{FieldCollector, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+type Tags = Vec<String>;
+type Maybe<T> = Option<T>;
+
+struct NonEmpty<T>(Vec<T>);
+
+impl<T> FieldCollector for NonEmpty<T> {
+    type Item = T;
+    type Builder = Vec<T>;
+
+    fn init() -> Self::Builder {
+        Vec::new()
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.push(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        (!builder.is_empty()).then_some(NonEmpty(builder))
+    }
+}
+
+#[derive(TryFromMultipart)]
+struct Data {
+    tags: Tags,
+    nickname: Maybe<String>,
+    queue: VecDeque<u32>,
+    unique: HashSet<u32>,
+    sorted: BTreeSet<u32>,
+    required: NonEmpty<String>,
+}
+
+#[tokio::test]
+async fn test_collector() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.tags, vec!["a", "b"]);
+        assert_eq!(data.nickname, Some("John".into()));
+        assert_eq!(data.queue, VecDeque::from([2, 1]));
+        assert_eq!(data.unique, HashSet::from([1, 2]));
+        assert_eq!(data.sorted, BTreeSet::from([1, 2]));
+        assert_eq!(data.required.0, vec!["data"]);
+    }
+
+    let form = Form::new()
+        .text("tags", "a")
+        .text("tags[]", "b")
+        .text("nickname", "John")
+        .text("queue", "2")
+        .text("queue[]", "1")
+        .text("unique", "2")
+        .text("unique", "1")
+        .text("unique", "2")
+        .text("sorted", "2")
+        .text("sorted", "1")
+        .text("required", "data");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_collector_missing() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("tags", "a"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'required' is required");
+}
+
+#[tokio::test]
+async fn test_collector_single_value_brackets() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.nickname, None);
+    }
+
+    // The `name[]` syntax is only accepted for fields that collect multiple values.
+    let form = Form::new().text("required", "data").text("nickname[]", "John");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -0,0 +1,108 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use reqwest::multipart::{Form, Part};
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(content_type = "image/png", content_type = "image/jpeg")]
+    avatar: Option<Bytes>,
+
+    #[form_data(content_type = "image/*")]
+    photos: Vec<Bytes>,
+
+    #[form_data(content_type = "text/plain")]
+    notes: HashMap<String, String>,
+
+    #[form_data(content_type = "text/plain")]
+    comment: Option<String>,
+
+    #[form_data(rest, content_type = "application/json")]
+    extra: Vec<Bytes>,
+}
+
+#[tokio::test]
+async fn test_content_type() {
+    struct Test {
+        field: &'static str,
+        content_type: Option<&'static str>,
+        status: StatusCode,
+        error: Option<&'static str>,
+    }
+
+    let tests = [
+        Test {
+            field: "avatar",
+            content_type: Some("image/png"),
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "avatar",
+            content_type: Some("IMAGE/JPEG; name=avatar"),
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "avatar",
+            content_type: Some("image/gif"),
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'avatar' has unsupported content type 'image/gif'"),
+        },
+        Test {
+            field: "photos",
+            content_type: Some("image/webp"),
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "photos[]",
+            content_type: Some("video/mp4"),
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'photos[]' has unsupported content type 'video/mp4'"),
+        },
+        Test {
+            field: "notes[first]",
+            content_type: Some("application/pdf"),
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'notes[first]' has unsupported content type 'application/pdf'"),
+        },
+        Test {
+            field: "comment",
+            content_type: None, // treated as text/plain
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "other",
+            content_type: Some("text/plain"),
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'other' has unsupported content type 'text/plain'"),
+        },
+    ];
+
+    for Test { field, content_type, status, error } in tests.into_iter() {
+        let part = match content_type {
+            Some(content_type) => Part::text("data").mime_str(content_type).unwrap(),
+            None => Part::text("data"),
+        };
+
+        let res =
+            TestClient::new(Router::new().route("/", post(|_: TypedMultipart<Data>| async {})))
+                .post("/")
+                .multipart(Form::new().part(field, part))
+                .send()
+                .await
+                .unwrap();
+
+        assert_eq!(res.status(), status, "{field}");
+        assert_eq!(res.text().await.unwrap(), error.unwrap_or(""));
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(delimiter = ",", trim)]
+    tags: Vec<String>,
+
+    #[form_data(delimiter = ";")]
+    numbers: Vec<u32>,
+}
+
+#[tokio::test]
+async fn test_delimiter() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.tags, vec!["a", "b", "c", "d"]);
+        assert_eq!(data.numbers, vec![1, 2, 3]);
+    };
+
+    let form = Form::new()
+        .text("tags", "a, b,,c")
+        .text("tags", "d")
+        .text("tags", "")
+        .text("numbers", "1;2")
+        .text("numbers", "3");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_delimiter_invalid_item() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let form = Form::new().text("numbers", "1;2").text("numbers", "3;x;5");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        res.text().await.unwrap(),
+        "field 'numbers[3]' must be of type 'u32': invalid digit found in string"
+    );
+}
@@ -0,0 +1,115 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{Presence, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+use std::collections::HashMap;
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(duplicates = "first")]
+struct First {
+    name: String,
+    nickname: Presence<String>,
+    meta: HashMap<String, String>,
+}
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(deny_unknown_fields)]
+struct DenyUnknownFields {
+    name: String,
+}
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(deny_duplicates)]
+struct DenyDuplicates {
+    name: String,
+}
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(deny_nameless)]
+struct DenyNameless {
+    name: String,
+}
+
+#[tokio::test]
+async fn test_duplicates_first() {
+    async fn handler(TypedMultipart(data): TypedMultipart<First>) {
+        assert_eq!(data.name, "John");
+        assert_eq!(data.nickname, Presence::Value("Johnny".into()));
+        assert_eq!(data.meta["color"], "red");
+    }
+
+    let form = Form::new()
+        .text("name", "John")
+        .text("name", "Jane")
+        .text("nickname", "Johnny")
+        .text("nickname", "")
+        .text("meta[color]", "red")
+        .text("meta[color]", "blue");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_deny_unknown_fields() {
+    async fn handler(TypedMultipart(data): TypedMultipart<DenyUnknownFields>) {
+        assert_eq!(data.name, "Jane");
+    }
+
+    let app = Router::new().route("/", post(handler));
+
+    let form = Form::new().text("name", "John").text("name", "Jane").text("", "nameless");
+    let res = TestClient::new(app.clone()).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let form = Form::new().text("name", "John").text("unknown", "data");
+    let res = TestClient::new(app).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'unknown' is not expected");
+}
+
+#[tokio::test]
+async fn test_deny_duplicates() {
+    async fn handler(TypedMultipart(data): TypedMultipart<DenyDuplicates>) {
+        assert_eq!(data.name, "John");
+    }
+
+    let app = Router::new().route("/", post(handler));
+
+    let form = Form::new().text("name", "John").text("unknown", "data").text("", "nameless");
+    let res = TestClient::new(app.clone()).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let form = Form::new().text("name", "John").text("name", "Jane");
+    let res = TestClient::new(app).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'name' is already present");
+}
+
+#[tokio::test]
+async fn test_deny_nameless() {
+    async fn handler(TypedMultipart(data): TypedMultipart<DenyNameless>) {
+        assert_eq!(data.name, "Jane");
+    }
+
+    let app = Router::new().route("/", post(handler));
+
+    let form = Form::new().text("name", "John").text("name", "Jane").text("unknown", "data");
+    let res = TestClient::new(app.clone()).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let form = Form::new().text("name", "John").text("", "nameless");
+    let res = TestClient::new(app).post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field name is empty");
+}
@@ -0,0 +1,145 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{FormOutcome, TryFromMultipart, TypedMultipartError};
+use reqwest::multipart::{Form, Part};
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct Data {
+    email: String,
+    age: u8,
+    tags: Vec<String>,
+    avatar: Option<Vec<u8>>,
+}
+
+async fn send(form: Form) -> (StatusCode, String) {
+    async fn handler(form: FormOutcome<Data>) -> String {
+        match form {
+            FormOutcome::Valid(data) => {
+                format!("{} {} {:?} {:?}", data.email, data.age, data.tags, data.avatar)
+            }
+            FormOutcome::Invalid(form) => {
+                assert_eq!(form.errors().len(), 1);
+                format!(
+                    "{:?} {:?} {:?} {:?} | {}",
+                    form.value("email"),
+                    form.value("age"),
+                    form.values("tags").collect::<Vec<_>>(),
+                    form.value("avatar"),
+                    form.error("age").map(TypedMultipartError::to_string).unwrap_or_default(),
+                )
+            }
+        }
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    (res.status(), res.text().await.unwrap())
+}
+
+fn avatar() -> Part {
+    Part::bytes(b"image".as_slice()).file_name("avatar.png")
+}
+
+#[tokio::test]
+async fn test_form_outcome_valid() {
+    let form = Form::new()
+        .text("email", "john@example.com")
+        .text("age", "42")
+        .text("tags", "a")
+        .text("tags", "b")
+        .part("avatar", avatar());
+
+    let (status, body) = send(form).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"john@example.com 42 ["a", "b"] Some([105, 109, 97, 103, 101])"#);
+}
+
+#[tokio::test]
+async fn test_form_outcome_invalid() {
+    let form = Form::new()
+        .text("email", "john@example.com")
+        .text("age", "old")
+        .text("tags", "a")
+        .text("tags", "b")
+        .part("avatar", avatar());
+
+    let (status, body) = send(form).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        r#"Some("john@example.com") Some("old") ["a", "b"] None | field 'age' must be of type 'u8': invalid digit found in string"#
+    );
+}
+
+#[tokio::test]
+async fn test_form_outcome_missing_field() {
+    let (status, body) = send(Form::new().text("email", "john@example.com")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"Some("john@example.com") None [] None | field 'age' is required"#);
+}
+
+#[tokio::test]
+async fn test_form_outcome_large_value() {
+    let form = Form::new().text("email", "x".repeat(65 * 1024)).text("age", "old");
+
+    let (status, body) = send(form).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        r#"None Some("old") [] None | field 'age' must be of type 'u8': invalid digit found in string"#
+    );
+}
+
+#[tokio::test]
+async fn test_form_outcome_multiple_errors() {
+    async fn handler(form: FormOutcome<Data>) -> String {
+        let FormOutcome::Invalid(form) = form else { panic!("should be invalid") };
+        let mut errors = form.errors().values().map(ToString::to_string).collect::<Vec<_>>();
+        errors.sort();
+        errors.join("\n")
+    }
+
+    let form = Form::new()
+        .text("age", "old")
+        .text("age", "42")
+        .text("tags", "a")
+        .text("color", "red")
+        .part("avatar", avatar());
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.text().await.unwrap(),
+        [
+            "field 'age' must be of type 'u8': invalid digit found in string",
+            "field 'color' is not expected",
+            "field 'email' is required",
+        ]
+        .join("\n")
+    );
+}
+
+#[tokio::test]
+async fn test_form_outcome_rejection() {
+    let form = Form::new().text("email", "john@example.com").part("", Part::text("nameless"));
+
+    let (status, body) = send(form).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "field name is empty");
+}
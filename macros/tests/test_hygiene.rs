@@ -9,10 +9,6 @@
 #![allow(dead_code)]
 #![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
 
-// `#[async_trait]` (third-party) requires these in scope; our own macros
-// must not require anything beyond what they qualify themselves.
-use ::std::boxed::Box;
-
 #[derive(::axum_typed_multipart::TryFromMultipart)]
 struct Lax {
     name: ::std::string::String,
@@ -28,6 +24,7 @@ struct Strict {
     name: ::std::string::String,
     tags: ::std::vec::Vec<::std::string::String>,
     opt: ::core::option::Option<::std::string::String>,
+    presence: ::axum_typed_multipart::Presence<::std::string::String>,
 }
 
 #[derive(::axum_typed_multipart::TryFromMultipart)]
@@ -37,6 +34,30 @@ struct WithLimitsAndFieldData {
     file: ::axum_typed_multipart::FieldData<::axum::body::Bytes>,
 }
 
+#[derive(::axum_typed_multipart::TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct WithCheckbox {
+    #[form_data(checkbox)]
+    agree: bool,
+    #[form_data(checkbox, lowercase)]
+    choices: ::std::vec::Vec<Plain>,
+    #[form_data(trim, nfc)]
+    name: ::std::string::String,
+    #[form_data(delimiter = ",")]
+    tags: ::std::vec::Vec<u32>,
+    meta: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+}
+
+#[derive(::axum_typed_multipart::TryFromMultipart)]
+struct WithRest {
+    name: ::std::string::String,
+    #[form_data(rest)]
+    rest: ::std::collections::BTreeMap<
+        ::std::string::String,
+        ::std::vec::Vec<::axum_typed_multipart::FieldData<::axum::body::Bytes>>,
+    >,
+}
+
 struct MyState;
 
 #[derive(::axum_typed_multipart::TryFromMultipart)]
@@ -0,0 +1,83 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{Presence, TryFromField, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(Debug, PartialEq, TryFromField)]
+#[try_from_field(rename_all = "lowercase")]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(trim)]
+    age: u32,
+
+    #[form_data(trim, lowercase)]
+    email: String,
+
+    #[form_data(trim, lowercase)]
+    color: Color,
+
+    #[form_data(nfc)]
+    city: Option<String>,
+
+    #[form_data(trim)]
+    nickname: Presence<String>,
+
+    #[form_data(lowercase, checkbox)]
+    colors: Vec<Color>,
+}
+
+#[tokio::test]
+async fn test_normalization() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.age, 42);
+        assert_eq!(data.email, "john@example.com");
+        assert_eq!(data.color, Color::Green);
+        assert_eq!(data.city.unwrap(), "Z\u{fc}rich");
+        assert_eq!(data.nickname, Presence::Empty);
+        assert_eq!(data.colors, vec![Color::Red]);
+    };
+
+    let form = Form::new()
+        .text("age", " 42 ")
+        .text("email", " John@Example.COM\n")
+        .text("color", "  GREEN ")
+        .text("city", "Zu\u{308}rich")
+        .text("nickname", "   ")
+        .text("colors", "")
+        .text("colors", "Red");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_normalization_invalid_utf8() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let part = reqwest::multipart::Part::bytes(vec![0x80]);
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().part("age", part))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,76 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{Presence, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    absent: Presence<u32>,
+    empty: Presence<u32>,
+    value: Presence<u32>,
+    #[form_data(default)]
+    default: axum_typed_multipart::Presence<String>,
+}
+
+#[allow(dead_code)]
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct StrictData {
+    value: Presence<String>,
+}
+
+#[tokio::test]
+async fn test_presence() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.absent, Presence::Absent);
+        assert_eq!(data.empty, Presence::Empty);
+        assert_eq!(data.value, Presence::Value(42));
+        assert_eq!(data.default, Presence::Absent);
+    };
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("empty", "").text("value", "42"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_presence_invalid() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("value", "invalid"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_presence_strict_duplicate_field() {
+    async fn handler(_: TypedMultipart<StrictData>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("value", "").text("value", "data"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'value' is already present");
+}
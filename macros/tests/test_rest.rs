@@ -0,0 +1,89 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+use std::collections::HashMap;
+
+type Extra = HashMap<String, Vec<String>>;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    name: String,
+    #[form_data(rest)]
+    extra: Vec<FieldData<Bytes>>,
+}
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct StrictData {
+    name: String,
+    #[form_data(rest, limit = "4B")]
+    extra: Extra,
+}
+
+#[tokio::test]
+async fn test_rest() {
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        assert_eq!(data.name, "John");
+        assert_eq!(data.extra.len(), 2);
+        assert_eq!(data.extra[0].metadata.name.as_deref(), Some("color"));
+        assert_eq!(data.extra[0].contents, "red");
+        assert_eq!(data.extra[1].metadata.name.as_deref(), Some("size"));
+        assert_eq!(data.extra[1].contents, "xl");
+    };
+
+    let form = Form::new().text("color", "red").text("name", "John").text("size", "xl");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_rest_map_strict() {
+    let handler = |TypedMultipart(data): TypedMultipart<StrictData>| async move {
+        assert_eq!(data.name, "John");
+        assert_eq!(data.extra.len(), 2);
+        assert_eq!(data.extra["tag"], vec!["a", "b"]);
+        assert_eq!(data.extra["size"], vec!["xl"]);
+    };
+
+    let form =
+        Form::new().text("tag", "a").text("name", "John").text("size", "xl").text("tag", "b");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_rest_limit() {
+    async fn handler(_: TypedMultipart<StrictData>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("name", "John").text("tag", "large"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(res.text().await.unwrap(), "field 'tag' is larger than 4 bytes");
+}
@@ -0,0 +1,173 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart, TypedMultipartError};
+use reqwest::multipart::{Form, Part};
+
+#[derive(Debug, PartialEq)]
+struct FieldError(String);
+
+impl From<TypedMultipartError> for FieldError {
+    fn from(err: TypedMultipartError) -> Self {
+        Self(err.to_string())
+    }
+}
+
+#[derive(TryFromMultipart)]
+struct Data {
+    name: String,
+    age: Result<u32, FieldError>,
+    #[form_data(limit = "8B")]
+    bio: Result<String, FieldError>,
+    #[form_data(trim)]
+    score: Option<Result<u8, FieldError>>,
+    tags: Vec<Result<u8, FieldError>>,
+}
+
+#[tokio::test]
+async fn test_result() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.name, "John");
+        assert_eq!(data.age, Ok(42));
+        assert_eq!(data.bio, Ok("Hello".into()));
+        assert_eq!(data.score, Some(Ok(7)));
+        assert_eq!(data.tags, vec![Ok(1), Ok(2)]);
+    }
+
+    let form = Form::new()
+        .text("name", "John")
+        .text("age", "42")
+        .text("bio", "Hello")
+        .text("score", " 7 ")
+        .text("tags", "1")
+        .text("tags", "2");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_result_captures_errors() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.name, "John");
+        assert!(data.age.unwrap_err().0.starts_with("field 'age' must be of type 'u32'"));
+        assert_eq!(data.bio, Err(FieldError("field 'bio' is larger than 8 bytes".into())));
+        assert!(data.score.unwrap().is_err());
+        assert_eq!(data.tags.len(), 2);
+        assert_eq!(data.tags[0], Ok(1));
+        assert!(data.tags[1].is_err());
+    }
+
+    let form = Form::new()
+        .text("name", "John")
+        .text("age", "forty-two")
+        .text("bio", "Hello, World!")
+        .text("score", "high")
+        .text("tags", "1")
+        .text("tags", "-2");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_result_missing_field() {
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.age, Err(FieldError("field 'age' is required".into())));
+        assert_eq!(data.bio, Err(FieldError("field 'bio' is required".into())));
+        assert_eq!(data.score, None);
+        assert_eq!(data.tags, vec![]);
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("name", "John"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_result_other_fields_still_fail() {
+    async fn handler(_: TypedMultipart<Data>) {
+        panic!("should not be called");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("age", "42"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'name' is required");
+}
+
+#[tokio::test]
+async fn test_result_captures_errors_before_parsing() {
+    #[derive(TryFromMultipart)]
+    struct Data {
+        #[form_data(trim, limit = "8B")]
+        name: Result<String, FieldError>,
+        #[form_data(lowercase)]
+        code: Result<String, FieldError>,
+        #[form_data(content_type = "image/png")]
+        avatar: Result<Bytes, FieldError>,
+        #[form_data(delimiter = ",", limit = "8B")]
+        tags: Vec<Result<u8, FieldError>>,
+        #[form_data(checkbox, content_type = "text/plain")]
+        flags: Vec<Result<String, FieldError>>,
+    }
+
+    async fn handler(TypedMultipart(data): TypedMultipart<Data>) {
+        assert_eq!(data.name, Err(FieldError("field 'name' is larger than 8 bytes".into())));
+        assert!(data.code.unwrap_err().0.starts_with("field 'code' must be of type"));
+        assert_eq!(
+            data.avatar,
+            Err(FieldError("field 'avatar' has unsupported content type 'text/plain'".into()))
+        );
+        assert_eq!(data.tags, vec![Err(FieldError("field 'tags' is larger than 8 bytes".into()))]);
+        assert_eq!(data.flags.len(), 2);
+        assert_eq!(data.flags[0], Ok("on".into()));
+        assert_eq!(
+            data.flags[1],
+            Err(FieldError("field 'flags' has unsupported content type 'image/png'".into()))
+        );
+    }
+
+    let form = Form::new()
+        .text("name", " Johnathan ")
+        .part("code", Part::bytes(vec![0xff, 0xfe]))
+        .text("avatar", "not an image")
+        .text("tags", "1,2,3,4,5")
+        .text("flags", "on")
+        .part("flags", Part::text("on").mime_str("image/png").unwrap());
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -0,0 +1,160 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart, TypedMultipartError};
+use reqwest::multipart::{Form, Part};
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+const PDF: &[u8] = b"%PDF-1.7\n";
+const TEXT: &[u8] = b"Hello, world!";
+
+#[allow(dead_code)]
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(sniff, content_type = "image/*")]
+    avatar: Option<Bytes>,
+
+    #[form_data(sniff)]
+    document: Option<Bytes>,
+}
+
+#[tokio::test]
+async fn test_sniff() {
+    struct Test {
+        field: &'static str,
+        contents: &'static [u8],
+        content_type: &'static str,
+        status: StatusCode,
+        error: Option<&'static str>,
+    }
+
+    let tests = [
+        Test {
+            field: "avatar",
+            contents: PNG,
+            content_type: "image/png",
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "avatar",
+            contents: PDF,
+            content_type: "image/png",
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'avatar' has unsupported content type 'application/pdf'"),
+        },
+        Test {
+            field: "avatar",
+            contents: TEXT,
+            content_type: "image/png",
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'avatar' has unsupported content type 'application/octet-stream'"),
+        },
+        Test {
+            field: "document",
+            contents: PDF,
+            content_type: "application/pdf",
+            status: StatusCode::OK,
+            error: None,
+        },
+        Test {
+            field: "document",
+            contents: PNG,
+            content_type: "application/pdf",
+            status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error: Some("field 'document' has unsupported content type 'image/png'"),
+        },
+        Test {
+            field: "document",
+            contents: TEXT,
+            content_type: "text/plain",
+            status: StatusCode::OK,
+            error: None,
+        },
+    ];
+
+    for Test { field, contents, content_type, status, error } in tests.into_iter() {
+        let part = Part::bytes(contents).mime_str(content_type).unwrap();
+        let res =
+            TestClient::new(Router::new().route("/", post(|_: TypedMultipart<Data>| async {})))
+                .post("/")
+                .multipart(Form::new().part(field, part))
+                .send()
+                .await
+                .unwrap();
+
+        assert_eq!(res.status(), status, "{field}");
+        assert_eq!(res.text().await.unwrap(), error.unwrap_or(""));
+    }
+}
+
+#[tokio::test]
+async fn test_sniffed_content_type() {
+    #[derive(TryFromMultipart)]
+    struct Data {
+        #[form_data(sniff)]
+        file: FieldData<Bytes>,
+        other: FieldData<Bytes>,
+    }
+
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        let sniffed_content_type = data.file.metadata.sniffed_content_type.unwrap();
+        assert_eq!(data.file.metadata.content_type.as_deref(), Some("image/png"));
+        assert_eq!(sniffed_content_type.get(), Some("image/png"));
+        assert_eq!(data.file.contents, PNG);
+        assert!(data.other.metadata.sniffed_content_type.is_none());
+    };
+
+    let form = Form::new()
+        .part("file", Part::bytes(PNG).mime_str("image/png").unwrap())
+        .part("other", Part::bytes(PNG).mime_str("image/png").unwrap());
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_sniff_result() {
+    #[derive(TryFromMultipart)]
+    struct Data {
+        #[form_data(sniff, content_type = "image/png")]
+        avatar: Result<Bytes, TypedMultipartError>,
+        #[form_data(sniff, content_type = "image/png")]
+        banner: Result<Bytes, TypedMultipartError>,
+    }
+
+    let handler = |TypedMultipart(data): TypedMultipart<Data>| async move {
+        let err = data.avatar.unwrap_err();
+        assert!(matches!(err, TypedMultipartError::InvalidContentType { .. }));
+        assert_eq!(
+            err.to_string(),
+            "field 'avatar' has unsupported content type 'application/pdf'"
+        );
+
+        let err = data.banner.unwrap_err();
+        assert_eq!(err.to_string(), "field 'banner' has unsupported content type 'text/plain'");
+    };
+
+    let form = Form::new()
+        .part("avatar", Part::bytes(PDF).mime_str("image/png").unwrap())
+        .part("banner", Part::bytes(PNG).mime_str("text/plain").unwrap());
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
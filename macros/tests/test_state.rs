@@ -6,8 +6,8 @@ use axum::routing::post;
 use axum::Router;
 use axum_test_helper::TestClient;
 use axum_typed_multipart::{
-    anyhow, async_trait, FieldData, TryFromField as _, TryFromFieldWithState, TryFromMultipart,
-    TypedMultipart, TypedMultipartError,
+    anyhow, FieldData, TryFromField as _, TryFromFieldWithState, TryFromMultipart, TypedMultipart,
+    TypedMultipartError,
 };
 use reqwest::multipart::Form;
 
@@ -18,7 +18,6 @@ struct AppState<T> {
 
 struct Position(u32);
 
-#[async_trait]
 impl<T> TryFromFieldWithState<AppState<T>> for Position
 where
     T: Into<u32> + Sync + Copy,
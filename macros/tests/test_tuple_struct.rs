@@ -0,0 +1,113 @@
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use axum_test_helper::TestClient;
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use reqwest::multipart::Form;
+
+#[derive(TryFromMultipart)]
+struct Login(#[form_data(field_name = "user")] String, #[form_data(field_name = "pass")] String);
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(positional)]
+struct Point(i32, i32, #[form_data(field_name = "label")] Option<String>, Vec<u8>);
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict)]
+struct Token(#[form_data(field_name = "token")] String);
+
+#[tokio::test]
+async fn test_tuple_struct() {
+    async fn handler(TypedMultipart(Login(user, pass)): TypedMultipart<Login>) {
+        assert_eq!(user, "john");
+        assert_eq!(pass, "secret");
+    }
+
+    let form = Form::new().text("user", "john").text("pass", "secret");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_tuple_struct_positional() {
+    async fn handler(TypedMultipart(point): TypedMultipart<Point>) {
+        assert_eq!(point.0, 1);
+        assert_eq!(point.1, -1);
+        assert_eq!(point.2, Some("origin".into()));
+        assert_eq!(point.3, b"data");
+    }
+
+    let form = Form::new().text("0", "1").text("1", "-1").text("label", "origin").text("3", "data");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_tuple_struct_missing_field() {
+    async fn handler(_: TypedMultipart<Point>) {
+        panic!("should not be called");
+    }
+
+    let form = Form::new().text("0", "1").text("3", "data");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field '1' is required");
+}
+
+#[tokio::test]
+async fn test_newtype_struct() {
+    async fn handler(TypedMultipart(Token(token)): TypedMultipart<Token>) {
+        assert_eq!(token, "abc");
+    }
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(Form::new().text("token", "abc"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_newtype_struct_strict() {
+    async fn handler(_: TypedMultipart<Token>) {
+        panic!("should not be called");
+    }
+
+    let form = Form::new().text("token", "abc").text("other", "value");
+
+    let res = TestClient::new(Router::new().route("/", post(handler)))
+        .post("/")
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.text().await.unwrap(), "field 'other' is not expected");
+}
@@ -0,0 +1,12 @@
+use axum_typed_multipart::TryFromMultipart;
+use std::collections::HashMap;
+
+type Flags = HashMap<String, bool>;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(checkbox)]
+    flags: Flags,
+}
+
+fn main() {}
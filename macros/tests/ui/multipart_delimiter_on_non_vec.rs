@@ -0,0 +1,9 @@
+use axum_typed_multipart::TryFromMultipart;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(delimiter = ",")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use axum_typed_multipart::TryFromMultipart;
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(strict, duplicates = "last")]
+struct Data {
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use axum_typed_multipart::TryFromMultipart;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(content_type = "image")]
+    field: String,
+}
+
+fn main() {}
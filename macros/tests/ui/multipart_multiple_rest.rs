@@ -0,0 +1,12 @@
+use axum::body::Bytes;
+use axum_typed_multipart::TryFromMultipart;
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(rest)]
+    first: Vec<Bytes>,
+    #[form_data(rest)]
+    second: Vec<Bytes>,
+}
+
+fn main() {}
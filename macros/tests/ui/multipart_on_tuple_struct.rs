@@ -1,6 +1,6 @@
 use axum_typed_multipart::TryFromMultipart;
 
 #[derive(TryFromMultipart)]
-struct Data(String);
+struct Data(#[form_data(field_name = "name")] String, u32);
 
 fn main() {}
@@ -0,0 +1,9 @@
+use axum_typed_multipart::TryFromMultipart;
+
+#[derive(TryFromMultipart)]
+#[try_from_multipart(positional)]
+struct Data {
+    name: String,
+}
+
+fn main() {}
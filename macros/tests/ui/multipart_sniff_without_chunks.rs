@@ -0,0 +1,21 @@
+use axum::extract::multipart::Field;
+use axum_typed_multipart::{TryFromField, TryFromMultipart, TypedMultipartError};
+
+struct Upload;
+
+impl TryFromField for Upload {
+    async fn try_from_field(
+        _: Field<'_>,
+        _: Option<usize>,
+    ) -> Result<Self, TypedMultipartError> {
+        Ok(Upload)
+    }
+}
+
+#[derive(TryFromMultipart)]
+struct Data {
+    #[form_data(sniff)]
+    upload: Upload,
+}
+
+fn main() {}
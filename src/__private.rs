@@ -0,0 +1,918 @@
+//! Support code for the derive macros.
+//!
+//! Everything in this module is an implementation detail of the code generated by
+//! [TryFromMultipart](crate::TryFromMultipart) and [TryFromField](crate::TryFromField) and is
+//! not covered by semver guarantees.
+
+use crate::form_outcome::ValueRecorder;
+use crate::{
+    util, FieldCollector, FieldMetadata, TryFromChunks, TryFromField, TryFromFieldWithState,
+    TypedMultipartError,
+};
+use axum::body::Bytes;
+use axum::extract::multipart::Field;
+use futures_util::stream;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use unicode_normalization::UnicodeNormalization;
+
+pub use futures_core::Stream;
+
+#[cfg(feature = "infer_0_19")]
+pub use crate::sniff::{Sniff, SniffRule};
+
+/// Transformations applied to the textual representation of a field before parsing it, as
+/// configured by the `trim`, `lowercase` and `nfc` parameters of `#[form_data(...)]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Normalization {
+    pub trim: bool,
+    pub lowercase: bool,
+    pub nfc: bool,
+}
+
+impl Normalization {
+    fn is_noop(&self) -> bool {
+        !self.trim && !self.lowercase && !self.nfc
+    }
+
+    fn apply(&self, mut text: String) -> String {
+        if self.nfc {
+            text = text.nfc().collect();
+        }
+        if self.trim {
+            text = text.trim().to_owned();
+        }
+        if self.lowercase {
+            text = text.to_lowercase();
+        }
+        text
+    }
+}
+
+/// Errors raised for the fields of a request, see
+/// [TryFromMultipartWithState::try_from_multipart_with_state_and_errors](crate::TryFromMultipartWithState::try_from_multipart_with_state_and_errors).
+///
+/// By default the first error aborts the parsing, while a collecting instance stores the errors
+/// related to a specific field so that the remaining fields can still be checked. A collecting
+/// instance also records the raw textual values read by the parsing helpers below, to be echoed
+/// back by [FormOutcome](crate::FormOutcome).
+#[derive(Debug, Default)]
+pub struct FieldErrors {
+    collect: bool,
+    errors: Vec<TypedMultipartError>,
+    recorder: Option<ValueRecorder>,
+}
+
+impl FieldErrors {
+    /// Create an instance storing all the field errors and recording the raw values.
+    pub fn collecting() -> Self {
+        Self { collect: true, errors: Vec::new(), recorder: Some(ValueRecorder::default()) }
+    }
+
+    /// Build the metadata of a field, recording its contents unless it is a file upload.
+    fn metadata(&self, field: &Field<'_>, limit_bytes: Option<usize>) -> FieldMetadata {
+        let metadata = FieldMetadata { limit_bytes, ..FieldMetadata::from(field) };
+        let recorder = self.recorder.clone().filter(|_| metadata.file_name.is_none());
+        FieldMetadata { recorder, ..metadata }
+    }
+
+    /// Take the raw values recorded so far.
+    pub(crate) fn values(&self) -> Vec<(String, String)> {
+        self.recorder.as_ref().map(ValueRecorder::take).unwrap_or_default()
+    }
+
+    /// Store the error, or return it if the errors are not collected or if it is not related to
+    /// a specific field.
+    pub fn record(&mut self, err: TypedMultipartError) -> Result<(), TypedMultipartError> {
+        if !self.collect || err.field_name().is_none() {
+            return Err(err);
+        }
+        self.errors.push(err);
+        Ok(())
+    }
+
+    /// Like [FieldErrors::record], returning [None] once the error has been stored.
+    pub fn check<T>(
+        &mut self,
+        res: Result<T, TypedMultipartError>,
+    ) -> Result<Option<T>, TypedMultipartError> {
+        match res {
+            Ok(value) => Ok(Some(value)),
+            Err(err) => self.record(err).map(|()| None),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Return the parsed value, or the first error if the parsing failed.
+    pub fn into_result<T>(self, value: Option<T>) -> Result<T, TypedMultipartError> {
+        match (value, self.errors.into_iter().next()) {
+            (Some(value), None) => Ok(value),
+            (_, Some(err)) => Err(err),
+            (None, None) => unreachable!("parsing failed without raising an error"),
+        }
+    }
+}
+
+impl IntoIterator for FieldErrors {
+    type Item = TypedMultipartError;
+    type IntoIter = std::vec::IntoIter<TypedMultipartError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+/// Extract the key from a field named `name[key]`, as sent by PHP/Rails-style forms.
+///
+/// Returns [None] if the field name does not follow the expected syntax or if the key is empty
+/// or contains nested brackets.
+pub fn bracket_key<'a>(field_name: &'a str, name: &str) -> Option<&'a str> {
+    let key = field_name.strip_prefix(name)?.strip_prefix('[')?.strip_suffix(']')?;
+    (!key.is_empty() && !key.contains(['[', ']'])).then_some(key)
+}
+
+/// Check the `Content-Type` of a field declared with `#[form_data(content_type = "...")]`
+/// against the allowed patterns, before its contents are read.
+///
+/// Parts without a `Content-Type` are treated as `text/plain`, as mandated by RFC 7578. Any
+/// content type is accepted when no patterns are supplied.
+fn check_content_type(field: &Field<'_>, allowed: &[&str]) -> Result<(), TypedMultipartError> {
+    let content_type = field.content_type().unwrap_or("text/plain");
+    if allowed.is_empty()
+        || allowed.iter().any(|pattern| util::content_type_matches(content_type, pattern))
+    {
+        return Ok(());
+    }
+
+    Err(TypedMultipartError::InvalidContentType {
+        field_name: field.name().unwrap_or_default().to_owned(),
+        content_type: content_type.to_owned(),
+    })
+}
+
+/// Read the contents of a field, applying the supplied [Normalization].
+///
+/// The field is only required to be valid UTF-8 when a transformation has to be applied.
+async fn read_field(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    normalization: Normalization,
+    errors: &FieldErrors,
+) -> Result<(FieldMetadata, Bytes), TypedMultipartError> {
+    check_content_type(&field, content_type)?;
+    let metadata = errors.metadata(&field, limit_bytes);
+
+    let bytes = if normalization.is_noop() {
+        Bytes::try_from_field_with_metadata(field, metadata.clone(), limit_bytes).await?
+    } else {
+        let text =
+            String::try_from_field_with_metadata(field, metadata.clone(), limit_bytes).await?;
+        Bytes::from(normalization.apply(text))
+    };
+
+    Ok((metadata, bytes))
+}
+
+/// Parse a field through its [TryFromFieldWithState] implementation.
+///
+/// Unlike calling the trait method on `_`, the type of the returned value can be inferred from
+/// how it is used.
+///
+/// The helpers below check the `Content-Type` of the field against the patterns set with
+/// `#[form_data(content_type = "...")]` before reading it. Errors raised before the contents are
+/// handed to the parser go through the `from_field_error` hook of the parsed type, so that
+/// `Result<T, E>` fields capture them as well.
+pub async fn parse<T, S>(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    state: &S,
+    errors: &FieldErrors,
+) -> Result<T, TypedMultipartError>
+where
+    T: TryFromFieldWithState<S>,
+{
+    if let Err(err) = check_content_type(&field, content_type) {
+        return T::from_field_error(err);
+    }
+    let metadata = errors.metadata(&field, limit_bytes);
+    T::try_from_field_with_state_and_metadata(field, metadata, limit_bytes, state).await
+}
+
+/// Parse a field declared with `#[form_data(sniff)]`, checking the content type detected from
+/// its first bytes according to the supplied [SniffRule].
+#[cfg(feature = "infer_0_19")]
+pub async fn sniffed<T>(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    rule: SniffRule,
+) -> Result<T, TypedMultipartError>
+where
+    T: Sniff,
+{
+    if let Err(err) = check_content_type(&field, content_type) {
+        return T::from_field_error(err);
+    }
+    let metadata = FieldMetadata {
+        limit_bytes,
+        sniffed_content_type: Some(crate::SniffedContentType::new(rule)),
+        ..FieldMetadata::from(&field)
+    };
+    T::try_from_sniffed_field(field, metadata, limit_bytes).await
+}
+
+/// Parse a field whose textual representation must be normalized before parsing.
+pub async fn normalized<T>(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    normalization: Normalization,
+    errors: &FieldErrors,
+) -> Result<T, TypedMultipartError>
+where
+    T: TryFromChunks,
+{
+    match read_field(field, limit_bytes, content_type, normalization, errors).await {
+        Ok((metadata, bytes)) => T::try_from_chunks(stream::iter([Ok(bytes)]), metadata).await,
+        Err(err) => T::from_field_error(err),
+    }
+}
+
+/// Parse a field declared with `#[form_data(delimiter = "...")]`, splitting its textual
+/// representation and parsing each piece separately.
+///
+/// Empty pieces are skipped. The `offset` is the number of items already collected for the
+/// field and is used to report the index of the offending item in case of errors, e.g.
+/// `tags[2]`. If the field itself cannot be read the error is handled as a single item.
+pub async fn delimited<T>(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    normalization: Normalization,
+    delimiter: &str,
+    offset: usize,
+    errors: &FieldErrors,
+) -> Result<Vec<T>, TypedMultipartError>
+where
+    T: TryFromChunks,
+{
+    if let Err(err) = check_content_type(&field, content_type) {
+        return T::from_field_error(err).map(|item| vec![item]);
+    }
+    let metadata = errors.metadata(&field, limit_bytes);
+    let text =
+        match String::try_from_field_with_metadata(field, metadata.clone(), limit_bytes).await {
+            Ok(text) => text,
+            Err(err) => return T::from_field_error(err).map(|item| vec![item]),
+        };
+    let name = metadata.name.clone().unwrap_or_default();
+    let mut items = Vec::new();
+
+    for piece in text.split(delimiter) {
+        let piece = normalization.apply(piece.to_owned());
+        if piece.is_empty() {
+            continue;
+        }
+
+        let index = offset + items.len();
+        let metadata = FieldMetadata { name: Some(format!("{name}[{index}]")), ..metadata.clone() };
+        let item = T::try_from_chunks(stream::iter([Ok(Bytes::from(piece))]), metadata).await?;
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+/// Parse a field declared with `#[form_data(checkbox)]`.
+///
+/// Any value other than an explicit false literal (e.g. `off` or `0`) marks the checkbox as
+/// checked, since browsers send `on` unless a custom `value` attribute is set.
+pub async fn checkbox(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    normalization: Normalization,
+    errors: &FieldErrors,
+) -> Result<bool, TypedMultipartError> {
+    let value: String = normalized(field, limit_bytes, content_type, normalization, errors).await?;
+    Ok(util::str_to_bool(value) != Some(false))
+}
+
+/// Parse a single entry of a checkbox group declared with `#[form_data(checkbox)]`.
+///
+/// Empty values are skipped, as they are commonly sent by hidden inputs to make sure the field
+/// is present even when no box is checked.
+pub async fn checkbox_item<T>(
+    field: Field<'_>,
+    limit_bytes: Option<usize>,
+    content_type: &[&str],
+    normalization: Normalization,
+    errors: &FieldErrors,
+) -> Result<Option<T>, TypedMultipartError>
+where
+    T: TryFromChunks,
+{
+    let res = read_field(field, limit_bytes, content_type, normalization, errors).await;
+    let (metadata, bytes) = match res {
+        Ok(res) => res,
+        Err(err) => return T::from_field_error(err).map(Some),
+    };
+
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+
+    T::try_from_chunks(stream::iter([Ok(bytes)]), metadata).await.map(Some)
+}
+
+/// Selects how the occurrences of a field of type `T` are combined, using autoref-based
+/// specialization: `(&&Dispatch::<T>(PhantomData)).collector()` resolves to a [Single] byte
+/// payload for `Vec<u8>`, to a [Fallible] for `Result<T, E>`, to an [Optional] for `Option<T>`,
+/// to a [Keyed] map for `HashMap<String, T>` and `BTreeMap<String, T>`, to a [Collector] when
+/// `T` implements [FieldCollector] and to [Single] otherwise. With a single reference
+/// [FieldCollector] takes precedence over byte payloads and [Optional].
+///
+/// Every collector provides the same methods, so the generated code does not depend on the
+/// selected one: [Keyed] maps are matched by the `name[key]` syntax and store one value per key,
+/// the other collectors are matched by name and use `()` as key.
+///
+/// This only works because the derived structs are not generic, so `T` is always a concrete type.
+pub struct Dispatch<T>(pub PhantomData<fn() -> T>);
+
+impl<T> Clone for Dispatch<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Dispatch<T> {}
+
+pub trait ViaBytes {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl ViaBytes for &&Dispatch<Vec<u8>> {
+    type Output = Single<Vec<u8>>;
+    fn collector(self) -> Self::Output {
+        Single(PhantomData)
+    }
+}
+
+pub trait ViaResult {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl<T, E> ViaResult for &&Dispatch<Result<T, E>> {
+    type Output = Fallible<T, E>;
+    fn collector(self) -> Self::Output {
+        Fallible(PhantomData)
+    }
+}
+
+pub trait ViaOption {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl<T> ViaOption for &&Dispatch<Option<T>> {
+    type Output = Optional<T>;
+    fn collector(self) -> Self::Output {
+        Optional(PhantomData)
+    }
+}
+
+pub trait ViaMap {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl<V, H> ViaMap for &&Dispatch<HashMap<String, V, H>> {
+    type Output = Keyed<HashMap<String, V, H>>;
+    fn collector(self) -> Self::Output {
+        Keyed(PhantomData)
+    }
+}
+
+impl<V> ViaMap for &&Dispatch<BTreeMap<String, V>> {
+    type Output = Keyed<BTreeMap<String, V>>;
+    fn collector(self) -> Self::Output {
+        Keyed(PhantomData)
+    }
+}
+
+pub trait ViaCollector {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl<T: FieldCollector> ViaCollector for &Dispatch<T> {
+    type Output = Collector<T>;
+    fn collector(self) -> Self::Output {
+        Collector(PhantomData)
+    }
+}
+
+pub trait ViaSingle {
+    type Output;
+    fn collector(self) -> Self::Output;
+}
+
+impl<T> ViaSingle for Dispatch<T> {
+    type Output = Single<T>;
+    fn collector(self) -> Self::Output {
+        Single(PhantomData)
+    }
+}
+
+/// Combines the occurrences of a field through its [FieldCollector] implementation.
+pub struct Collector<T>(PhantomData<fn() -> T>);
+
+impl<T: FieldCollector> Collector<T> {
+    pub fn init(&self) -> T::Builder {
+        T::init()
+    }
+
+    pub fn multiple(&self) -> bool {
+        T::MULTIPLE
+    }
+
+    pub fn push(&self, builder: &mut T::Builder, item: T::Item) {
+        T::push(builder, item);
+    }
+
+    pub fn key(&self, field_name: &str, name: &str) -> Option<()> {
+        matches_name(field_name, name, self.multiple())
+    }
+
+    pub fn is_duplicate(&self, _: &T::Builder, _: &(), seen: bool) -> bool {
+        !self.multiple() && seen
+    }
+
+    pub fn insert(&self, builder: &mut T::Builder, _: (), item: T::Item) {
+        T::push(builder, item);
+    }
+
+    pub fn rest_key(&self, _: &str) {}
+
+    pub fn push_rest(&self, builder: &mut T::Builder, _: (), item: T::Item) {
+        T::push(builder, item);
+    }
+
+    pub fn finish(&self, builder: T::Builder) -> Option<T> {
+        T::finish(builder)
+    }
+
+    /// Whether an occurrence must be collected after `count` occurrences were already found.
+    pub fn accepts(&self, count: usize) -> bool {
+        T::COUNT.is_none_or(|expected| count < expected)
+    }
+
+    pub fn check_count(&self, field_name: &str, count: usize) -> Result<(), TypedMultipartError> {
+        match T::COUNT {
+            Some(expected) if count != expected => Err(TypedMultipartError::WrongFieldCount {
+                field_name: field_name.to_owned(),
+                expected,
+                actual: count,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn missing(&self, field_name: &str) -> Result<T, TypedMultipartError> {
+        Err(TypedMultipartError::MissingField { field_name: field_name.to_owned() })
+    }
+
+    pub async fn checkbox(
+        &self,
+        field: Field<'_>,
+        limit_bytes: Option<usize>,
+        content_type: &[&str],
+        normalization: Normalization,
+        errors: &FieldErrors,
+    ) -> Result<Option<T::Item>, TypedMultipartError>
+    where
+        T::Item: TryFromChunks,
+    {
+        checkbox_item(field, limit_bytes, content_type, normalization, errors).await
+    }
+}
+
+/// Stores a required field, keeping the last occurrence.
+pub struct Single<T>(PhantomData<fn() -> T>);
+
+impl<T> Single<T> {
+    pub fn init(&self) -> Option<T> {
+        None
+    }
+
+    pub fn multiple(&self) -> bool {
+        false
+    }
+
+    pub fn push(&self, builder: &mut Option<T>, item: T) {
+        *builder = Some(item);
+    }
+
+    pub fn key(&self, field_name: &str, name: &str) -> Option<()> {
+        matches_name(field_name, name, false)
+    }
+
+    pub fn is_duplicate(&self, _: &Option<T>, _: &(), seen: bool) -> bool {
+        seen
+    }
+
+    pub fn insert(&self, builder: &mut Option<T>, _: (), item: T) {
+        *builder = Some(item);
+    }
+
+    /// `rest` fields must collect multiple values, the bound reports the missing
+    /// [FieldCollector] implementation.
+    pub fn rest_key(&self, _: &str)
+    where
+        T: FieldCollector,
+    {
+    }
+
+    pub fn push_rest(&self, _: &mut Option<T>, _: (), _: T)
+    where
+        T: FieldCollector,
+    {
+        unreachable!("`Single` is only selected for types not implementing `FieldCollector`")
+    }
+
+    pub fn finish(&self, builder: Option<T>) -> Option<T> {
+        builder
+    }
+
+    pub fn accepts(&self, _: usize) -> bool {
+        true
+    }
+
+    pub fn check_count(&self, _: &str, _: usize) -> Result<(), TypedMultipartError> {
+        Ok(())
+    }
+
+    pub fn missing(&self, field_name: &str) -> Result<T, TypedMultipartError> {
+        Err(TypedMultipartError::MissingField { field_name: field_name.to_owned() })
+    }
+}
+
+impl Single<bool> {
+    pub async fn checkbox(
+        &self,
+        field: Field<'_>,
+        limit_bytes: Option<usize>,
+        content_type: &[&str],
+        normalization: Normalization,
+        errors: &FieldErrors,
+    ) -> Result<Option<bool>, TypedMultipartError> {
+        checkbox(field, limit_bytes, content_type, normalization, errors).await.map(Some)
+    }
+}
+
+/// Stores a `Result<T, E>` field, keeping the last occurrence and capturing a missing field as an
+/// error instead of failing the whole request.
+pub struct Fallible<T, E>(PhantomData<fn() -> Result<T, E>>);
+
+impl<T, E> Fallible<T, E>
+where
+    E: From<TypedMultipartError>,
+{
+    pub fn init(&self) -> Option<Result<T, E>> {
+        None
+    }
+
+    pub fn multiple(&self) -> bool {
+        false
+    }
+
+    pub fn push(&self, builder: &mut Option<Result<T, E>>, item: Result<T, E>) {
+        *builder = Some(item);
+    }
+
+    pub fn key(&self, field_name: &str, name: &str) -> Option<()> {
+        matches_name(field_name, name, false)
+    }
+
+    pub fn is_duplicate(&self, _: &Option<Result<T, E>>, _: &(), seen: bool) -> bool {
+        seen
+    }
+
+    pub fn insert(&self, builder: &mut Option<Result<T, E>>, _: (), item: Result<T, E>) {
+        *builder = Some(item);
+    }
+
+    pub fn finish(&self, builder: Option<Result<T, E>>) -> Option<Result<T, E>> {
+        builder
+    }
+
+    pub fn accepts(&self, _: usize) -> bool {
+        true
+    }
+
+    pub fn check_count(&self, _: &str, _: usize) -> Result<(), TypedMultipartError> {
+        Ok(())
+    }
+
+    pub fn missing(&self, field_name: &str) -> Result<Result<T, E>, TypedMultipartError> {
+        let err = TypedMultipartError::MissingField { field_name: field_name.to_owned() };
+        Ok(Err(err.into()))
+    }
+}
+
+/// Stores an [Option] field, like [Collector] does, but rejecting `#[form_data(checkbox)]`: an
+/// absent checkbox is unchecked rather than unknown, so `bool` must be used instead.
+pub struct Optional<T>(PhantomData<fn() -> T>);
+
+impl<T> Optional<T> {
+    pub fn init(&self) -> Option<T> {
+        None
+    }
+
+    pub fn multiple(&self) -> bool {
+        false
+    }
+
+    pub fn push(&self, builder: &mut Option<T>, item: T) {
+        *builder = Some(item);
+    }
+
+    pub fn key(&self, field_name: &str, name: &str) -> Option<()> {
+        matches_name(field_name, name, false)
+    }
+
+    pub fn is_duplicate(&self, _: &Option<T>, _: &(), seen: bool) -> bool {
+        seen
+    }
+
+    pub fn insert(&self, builder: &mut Option<T>, _: (), item: T) {
+        *builder = Some(item);
+    }
+
+    pub fn finish(&self, builder: Option<T>) -> Option<Option<T>> {
+        Some(builder)
+    }
+
+    pub fn accepts(&self, _: usize) -> bool {
+        true
+    }
+
+    pub fn check_count(&self, _: &str, _: usize) -> Result<(), TypedMultipartError> {
+        Ok(())
+    }
+
+    pub fn missing(&self, field_name: &str) -> Result<Option<T>, TypedMultipartError> {
+        Err(TypedMultipartError::MissingField { field_name: field_name.to_owned() })
+    }
+
+    pub async fn checkbox(
+        &self,
+        _: Field<'_>,
+        _: Option<usize>,
+        _: &[&str],
+        _: Normalization,
+        _: &FieldErrors,
+    ) -> Result<Option<T>, TypedMultipartError>
+    where
+        Option<T>: Checkbox,
+    {
+        unreachable!("`Checkbox` is never implemented")
+    }
+}
+
+/// Maps storing the occurrences of a field by key, see [Keyed].
+pub trait Map: Default {
+    type Value;
+
+    fn contains_key(&self, key: &str) -> bool;
+
+    fn insert(&mut self, key: String, value: Self::Value);
+
+    fn entry(&mut self, key: String) -> &mut Self::Value
+    where
+        Self::Value: Default;
+}
+
+impl<V, H> Map for HashMap<String, V, H>
+where
+    H: BuildHasher + Default,
+{
+    type Value = V;
+
+    fn contains_key(&self, key: &str) -> bool {
+        HashMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: String, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn entry(&mut self, key: String) -> &mut V
+    where
+        V: Default,
+    {
+        HashMap::entry(self, key).or_default()
+    }
+}
+
+impl<V> Map for BTreeMap<String, V> {
+    type Value = V;
+
+    fn contains_key(&self, key: &str) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: String, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn entry(&mut self, key: String) -> &mut V
+    where
+        V: Default,
+    {
+        BTreeMap::entry(self, key).or_default()
+    }
+}
+
+/// Stores the occurrences of a map field.
+///
+/// Regular fields are matched by the `name[key]` syntax and keep one value per key, while `rest`
+/// fields are keyed by the full field name and collect all the values sent with that name.
+pub struct Keyed<M>(PhantomData<fn() -> M>);
+
+impl<M: Map> Keyed<M> {
+    pub fn init(&self) -> M {
+        M::default()
+    }
+
+    pub fn key(&self, field_name: &str, name: &str) -> Option<String> {
+        bracket_key(field_name, name).map(String::from)
+    }
+
+    pub fn is_duplicate(&self, builder: &M, key: &str, _: bool) -> bool {
+        builder.contains_key(key)
+    }
+
+    pub fn insert(&self, builder: &mut M, key: String, value: M::Value) {
+        builder.insert(key, value);
+    }
+
+    pub fn rest_key(&self, field_name: &str) -> String {
+        field_name.to_owned()
+    }
+
+    pub fn push_rest(&self, builder: &mut M, key: String, item: <M::Value as FieldCollector>::Item)
+    where
+        M::Value: FieldCollector<Builder = M::Value> + Default,
+    {
+        M::Value::push(builder.entry(key), item);
+    }
+
+    pub fn finish(&self, builder: M) -> Option<M> {
+        Some(builder)
+    }
+
+    pub fn accepts(&self, _: usize) -> bool {
+        true
+    }
+
+    pub fn check_count(&self, _: &str, _: usize) -> Result<(), TypedMultipartError> {
+        Ok(())
+    }
+
+    pub fn missing(&self, field_name: &str) -> Result<M, TypedMultipartError> {
+        Err(TypedMultipartError::MissingField { field_name: field_name.to_owned() })
+    }
+
+    pub async fn checkbox(
+        &self,
+        _: Field<'_>,
+        _: Option<usize>,
+        _: &[&str],
+        _: Normalization,
+        _: &FieldErrors,
+    ) -> Result<Option<M::Value>, TypedMultipartError>
+    where
+        M: Checkbox,
+    {
+        unreachable!("`Checkbox` is never implemented")
+    }
+}
+
+/// Reports a `#[form_data(checkbox)]` parameter on a field type that does not support it, see
+/// [Optional::checkbox] and [Keyed::checkbox]. Never implemented.
+#[diagnostic::on_unimplemented(
+    message = "`checkbox` is not supported on `{Self}` fields",
+    note = "`checkbox` requires a `bool` field or a collection of multiple values such as `Vec`"
+)]
+pub trait Checkbox {}
+
+/// Whether the field name matches the supplied name, or the `name[]` syntax for fields accepting
+/// multiple values.
+fn matches_name(field_name: &str, name: &str, multiple: bool) -> Option<()> {
+    let matches = field_name == name
+        || (multiple && field_name.strip_prefix(name).is_some_and(|suffix| suffix == "[]"));
+    matches.then_some(())
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        let normalization = Normalization { trim: true, lowercase: true, nfc: true };
+        assert_eq!(normalization.apply(" E\u{301}COLE\t".into()), "\u{e9}cole");
+        assert_eq!(Normalization::default().apply(" Data ".into()), " Data ");
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)] // the borrow is what drives the dispatch
+    fn test_dispatch() {
+        let collector = (&&Dispatch::<Vec<u32>>(PhantomData)).collector();
+        let mut builder = collector.init();
+        collector.push(&mut builder, 1);
+        assert!(collector.multiple());
+        assert_eq!(collector.finish(builder), Some(vec![1]));
+
+        let collector = (&&Dispatch::<Vec<u8>>(PhantomData)).collector();
+        let mut builder = collector.init();
+        collector.push(&mut builder, vec![1]);
+        assert!(!collector.multiple());
+        assert_eq!(collector.finish(builder), Some(vec![1]));
+
+        let collector = (&Dispatch::<Vec<u8>>(PhantomData)).collector();
+        assert!(collector.multiple());
+
+        let collector = (&&Dispatch::<u8>(PhantomData)).collector();
+        let mut builder = collector.init();
+        assert_eq!(collector.finish(builder), None);
+        collector.push(&mut builder, 1);
+        collector.push(&mut builder, 2);
+        assert!(!collector.multiple());
+        assert_eq!(collector.finish(builder), Some(2));
+
+        let collector = (&&Dispatch::<Result<u8, TypedMultipartError>>(PhantomData)).collector();
+        let builder = collector.init();
+        assert!(!collector.multiple());
+        assert_eq!(collector.finish(builder).map(|res| res.ok()), None);
+        let res = collector.missing("name").unwrap();
+        assert!(matches!(res, Err(TypedMultipartError::MissingField { .. })));
+
+        let collector = (&&Dispatch::<Option<u8>>(PhantomData)).collector();
+        let mut builder = collector.init();
+        assert_eq!(collector.finish(builder), Some(None));
+        collector.insert(&mut builder, (), 1);
+        assert!(collector.is_duplicate(&builder, &(), true));
+        assert_eq!(collector.finish(builder), Some(Some(1)));
+
+        let collector = (&&Dispatch::<HashMap<String, u8>>(PhantomData)).collector();
+        let mut builder = collector.init();
+        let key = collector.key("meta[color]", "meta").unwrap();
+        assert!(!collector.is_duplicate(&builder, &key, true));
+        collector.insert(&mut builder, key, 1);
+        assert!(collector.is_duplicate(&builder, "color", false));
+        assert_eq!(collector.key("meta", "meta"), None);
+        assert_eq!(collector.finish(builder), Some(HashMap::from([("color".into(), 1)])));
+
+        let collector = (&Dispatch::<BTreeMap<String, Vec<u8>>>(PhantomData)).collector();
+        let mut builder = collector.init();
+        for item in [1, 2] {
+            let key = collector.rest_key("extra");
+            collector.push_rest(&mut builder, key, item);
+        }
+        assert_eq!(collector.finish(builder), Some(BTreeMap::from([("extra".into(), vec![1, 2])])));
+    }
+
+    #[test]
+    fn test_key() {
+        let collector = (&&Dispatch::<Vec<u8>>(PhantomData)).collector();
+        assert_eq!(collector.key("data", "data"), Some(()));
+        assert_eq!(collector.key("data[]", "data"), None);
+
+        let collector = (&Dispatch::<Vec<u32>>(PhantomData)).collector();
+        assert_eq!(collector.key("data", "data"), Some(()));
+        assert_eq!(collector.key("data[]", "data"), Some(()));
+        assert_eq!(collector.key("data[0]", "data"), None);
+        assert_eq!(collector.key("database", "data"), None);
+    }
+
+    #[test]
+    fn test_bracket_key() {
+        assert_eq!(bracket_key("meta[color]", "meta"), Some("color"));
+        assert_eq!(bracket_key("meta[]", "meta"), None);
+        assert_eq!(bracket_key("meta[a][b]", "meta"), None);
+        assert_eq!(bracket_key("meta[color", "meta"), None);
+        assert_eq!(bracket_key("metadata[color]", "meta"), None);
+        assert_eq!(bracket_key("meta", "meta"), None);
+    }
+}
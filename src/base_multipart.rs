@@ -70,7 +70,6 @@ where
 mod tests {
     use super::*;
     use crate::TryFromMultipart;
-    use async_trait::async_trait;
     use axum::extract::Multipart;
     use axum::routing::post;
     use axum::Router;
@@ -79,7 +78,6 @@ mod tests {
 
     struct Data(String);
 
-    #[async_trait]
     impl TryFromMultipart for Data {
         async fn try_from_multipart(_: &mut Multipart) -> Result<Self, TypedMultipartError> {
             Ok(Self(String::from("data")))
@@ -0,0 +1,381 @@
+use crate::{
+    BaseMultipart, FieldData, FieldKind, FieldMetadata, FormSchema, TryFromChunks, TryFromField,
+    TryFromMultipart, TypedMultipartError,
+};
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Multipart, Request};
+use futures_util::stream;
+use std::collections::HashMap;
+
+/// Extractor for multipart forms whose shape is only known at runtime.
+///
+/// All named fields are collected in the order they were received, and can then be looked up by
+/// name and parsed into any type implementing [TryFromChunks]. The contents are stored as `T`,
+/// which defaults to [Bytes] but can be set to e.g.
+/// [NamedTempFile](tempfile_3::NamedTempFile) to avoid keeping large uploads in memory.
+///
+/// Use [DynamicMultipart::from_multipart_with_schema] to validate the request against a
+/// [FormSchema] built at runtime.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum::extract::Multipart;
+/// use axum_typed_multipart::{
+///     DynamicMultipart, FieldKind, FieldSchema, FormSchema, TypedMultipartError,
+/// };
+///
+/// async fn handle_data(form: DynamicMultipart) -> Result<(), TypedMultipartError> {
+///     let name: String = form.get("name").await?;
+///     let tags: Vec<String> = form.get_all("tags").await?;
+///     println!("name: {name}, tags: {tags:?}");
+///     Ok(())
+/// }
+///
+/// async fn handle_data_with_schema(mut multipart: Multipart) -> Result<(), TypedMultipartError> {
+///     let schema = FormSchema::new()
+///         .field(FieldSchema::new("name").kind(FieldKind::Text).required(true))
+///         .field(FieldSchema::new("age").kind(FieldKind::Integer));
+///
+///     let form = DynamicMultipart::from_multipart_with_schema(&mut multipart, &schema).await?;
+///     let age: Option<u8> = form.get_optional("age").await?;
+///     println!("age: {age:?}");
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DynamicMultipart<T = Bytes> {
+    fields: Vec<FieldData<T>>,
+    /// Positions in `fields` of the occurrences of each name, in the order they were received.
+    indices: HashMap<String, Vec<usize>>,
+}
+
+impl<T> DynamicMultipart<T> {
+    /// Returns all the fields in the order they were received.
+    pub fn fields(&self) -> &[FieldData<T>] {
+        &self.fields
+    }
+
+    /// Consumes the form, returning all the fields in the order they were received.
+    pub fn into_fields(self) -> Vec<FieldData<T>> {
+        self.fields
+    }
+
+    /// Returns the names of the received fields, without duplicates.
+    pub fn names(&self) -> Vec<&str> {
+        let names = self.fields.iter().enumerate().filter_map(|(index, field)| {
+            let name = field.metadata.name.as_deref()?;
+            (self.indices[name].first() == Some(&index)).then_some(name)
+        });
+        names.collect()
+    }
+
+    /// Returns `true` if at least one field with the supplied name was received.
+    pub fn contains(&self, name: &str) -> bool {
+        self.indices.contains_key(name)
+    }
+
+    /// Returns the last field with the supplied name, if any.
+    pub fn field(&self, name: &str) -> Option<&FieldData<T>> {
+        let index = self.indices.get(name)?.last()?;
+        Some(&self.fields[*index])
+    }
+
+    /// Returns all the fields with the supplied name, in the order they were received.
+    pub fn fields_named<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a FieldData<T>> {
+        let indices = self.indices.get(name).map(Vec::as_slice).unwrap_or_default();
+        indices.iter().map(|index| &self.fields[*index])
+    }
+}
+
+impl<T> DynamicMultipart<T>
+where
+    T: TryFromChunks + Send + Sync,
+{
+    /// Collects all the named fields of the request, without any validation.
+    pub async fn from_multipart(multipart: &mut Multipart) -> Result<Self, TypedMultipartError> {
+        Self::collect(multipart, None).await
+    }
+
+    /// Collects the fields of the request, validating them against the supplied [FormSchema].
+    pub async fn from_multipart_with_schema(
+        multipart: &mut Multipart,
+        schema: &FormSchema,
+    ) -> Result<Self, TypedMultipartError> {
+        Self::collect(multipart, Some(schema)).await
+    }
+
+    async fn collect(
+        multipart: &mut Multipart,
+        schema: Option<&FormSchema>,
+    ) -> Result<Self, TypedMultipartError> {
+        let strict = schema.is_some_and(|schema| schema.strict);
+        let mut fields: Vec<FieldData<T>> = Vec::new();
+        let mut indices: HashMap<String, Vec<usize>> = HashMap::new();
+
+        while let Some(field) = multipart.next_field().await? {
+            let field_name = match field.name() {
+                Some(name) if !name.is_empty() => name.to_owned(),
+                _ if strict => return Err(TypedMultipartError::NamelessField),
+                _ => continue,
+            };
+
+            // Position of the previous occurrence replaced by this one, for single valued fields.
+            let mut replaced = None;
+            let (kind, limit_bytes) = match schema {
+                None => (FieldKind::Bytes, None),
+                Some(schema) => match schema.get(&field_name) {
+                    Some(field_schema) => {
+                        if !field_schema.multiple {
+                            replaced = indices.get(&field_name).and_then(|v| v.first().copied());
+                        }
+                        if replaced.is_some() && strict {
+                            return Err(TypedMultipartError::DuplicateField { field_name });
+                        }
+
+                        (field_schema.kind, field_schema.limit_bytes)
+                    }
+                    None if strict => {
+                        return Err(TypedMultipartError::UnknownField { field_name });
+                    }
+                    None => continue,
+                },
+            };
+
+            let metadata = FieldMetadata { limit_bytes, ..FieldMetadata::from(&field) };
+            let contents = if kind == FieldKind::Bytes {
+                T::try_from_field_with_metadata(field, metadata.clone(), limit_bytes).await?
+            } else {
+                let bytes =
+                    Bytes::try_from_field_with_metadata(field, metadata.clone(), limit_bytes)
+                        .await?;
+                kind.validate(&bytes, &metadata).await?;
+                T::try_from_chunks(stream::iter([Ok(bytes)]), metadata.clone()).await?
+            };
+
+            let field = FieldData { metadata, contents };
+            match replaced {
+                Some(index) => fields[index] = field,
+                None => {
+                    indices.entry(field_name).or_default().push(fields.len());
+                    fields.push(field);
+                }
+            }
+        }
+
+        if let Some(schema) = schema {
+            for field_schema in schema.fields.iter().filter(|field| field.required) {
+                if !indices.contains_key(&field_schema.name) {
+                    let field_name = field_schema.name.clone();
+                    return Err(TypedMultipartError::MissingField { field_name });
+                }
+            }
+        }
+
+        Ok(Self { fields, indices })
+    }
+}
+
+impl DynamicMultipart<Bytes> {
+    /// Parses the last field with the supplied name.
+    ///
+    /// Returns [MissingField](TypedMultipartError::MissingField) if the field is not present.
+    pub async fn get<U>(&self, name: &str) -> Result<U, TypedMultipartError>
+    where
+        U: TryFromChunks,
+    {
+        match self.get_optional(name).await? {
+            Some(value) => Ok(value),
+            None => Err(TypedMultipartError::MissingField { field_name: name.to_owned() }),
+        }
+    }
+
+    /// Parses the last field with the supplied name, if present.
+    pub async fn get_optional<U>(&self, name: &str) -> Result<Option<U>, TypedMultipartError>
+    where
+        U: TryFromChunks,
+    {
+        match self.field(name) {
+            Some(field) => parse(field).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses all the fields with the supplied name, in the order they were received.
+    pub async fn get_all<U>(&self, name: &str) -> Result<Vec<U>, TypedMultipartError>
+    where
+        U: TryFromChunks,
+    {
+        let mut values = Vec::new();
+        for field in self.fields_named(name) {
+            values.push(parse(field).await?);
+        }
+        Ok(values)
+    }
+}
+
+async fn parse<U>(field: &FieldData<Bytes>) -> Result<U, TypedMultipartError>
+where
+    U: TryFromChunks,
+{
+    let chunks = stream::iter([Ok(field.contents.clone())]);
+    U::try_from_chunks(chunks, field.metadata.clone()).await
+}
+
+impl<T> TryFromMultipart for DynamicMultipart<T>
+where
+    T: TryFromChunks + Send + Sync,
+{
+    async fn try_from_multipart(multipart: &mut Multipart) -> Result<Self, TypedMultipartError> {
+        Self::from_multipart(multipart).await
+    }
+}
+
+impl<T, S> FromRequest<S> for DynamicMultipart<T>
+where
+    T: TryFromChunks + Send + Sync,
+    S: Send + Sync,
+{
+    type Rejection = TypedMultipartError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let base = BaseMultipart::<Self, Self::Rejection>::from_request(req, state).await?;
+        Ok(base.data)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use crate::FieldSchema;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use axum_test_helper::TestClient;
+    use reqwest::multipart::{Form, Part};
+    use std::sync::Arc;
+
+    fn schema() -> FormSchema {
+        FormSchema::new()
+            .field(FieldSchema::new("name").kind(FieldKind::Text).required(true))
+            .field(FieldSchema::new("age").kind(FieldKind::Integer))
+            .field(FieldSchema::new("tags").multiple(true).limit_bytes(Some(8)))
+    }
+
+    async fn send(schema: FormSchema, form: Form) -> (StatusCode, String) {
+        let schema = Arc::new(schema);
+        let handler = |mut multipart: Multipart| async move {
+            let form =
+                DynamicMultipart::<Bytes>::from_multipart_with_schema(&mut multipart, &schema)
+                    .await?;
+            let tags: Vec<String> = form.get_all("tags").await?;
+            Ok::<_, TypedMultipartError>(format!("{:?} {tags:?}", form.names()))
+        };
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(form)
+            .send()
+            .await
+            .unwrap();
+
+        (res.status(), res.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_multipart() {
+        async fn handler(form: DynamicMultipart) {
+            assert_eq!(form.names(), ["name", "tags"]);
+            assert_eq!(form.fields().len(), 3);
+            assert!(form.contains("tags"));
+            assert!(!form.contains("unknown"));
+
+            assert_eq!(form.get::<String>("name").await.unwrap(), "John");
+            assert_eq!(form.get_all::<u8>("tags").await.unwrap(), [1, 2]);
+            assert_eq!(form.get_optional::<String>("unknown").await.unwrap(), None);
+
+            let res = form.get::<String>("unknown").await;
+            assert!(matches!(res, Err(TypedMultipartError::MissingField { .. })));
+
+            let res = form.get::<u8>("name").await;
+            assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+
+            let field = form.field("tags").unwrap();
+            assert_eq!(field.contents, "2");
+        }
+
+        let form = Form::new()
+            .text("name", "John")
+            .text("tags", "1")
+            .part("", Part::text("nameless"))
+            .text("tags", "2");
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(form)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_multipart_with_schema() {
+        let form = Form::new()
+            .text("name", "John")
+            .text("name", "Jane")
+            .text("unknown", "data")
+            .text("tags", "a")
+            .text("tags", "b");
+
+        let (status, body) = send(schema(), form).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"["name", "tags"] ["a", "b"]"#);
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_multipart_with_schema_errors() {
+        let cases = [
+            (Form::new().text("age", "42"), StatusCode::BAD_REQUEST, "field 'name' is required"),
+            (
+                Form::new().text("name", "John").text("age", "old"),
+                StatusCode::BAD_REQUEST,
+                "field 'age' must be of type 'i64': invalid digit found in string",
+            ),
+            (
+                Form::new().text("name", "John").text("tags", "too long tag"),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "field 'tags' is larger than 8 bytes",
+            ),
+        ];
+
+        for (form, expected_status, expected_body) in cases {
+            let (status, body) = send(schema(), form).await;
+            assert_eq!(status, expected_status);
+            assert_eq!(body, expected_body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_multipart_with_strict_schema() {
+        let cases = [
+            (
+                Form::new().text("name", "John").text("name", "Jane"),
+                "field 'name' is already present",
+            ),
+            (
+                Form::new().text("name", "John").text("unknown", "data"),
+                "field 'unknown' is not expected",
+            ),
+            (Form::new().text("name", "John").part("", Part::text("data")), "field name is empty"),
+        ];
+
+        for (form, expected_body) in cases {
+            let (status, body) = send(schema().strict(true), form).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body, expected_body);
+        }
+    }
+}
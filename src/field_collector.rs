@@ -0,0 +1,220 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Types that can be built from zero or more occurrences of a field.
+///
+/// The [TryFromMultipart](crate::TryFromMultipart) derive macro uses this trait to decide how the
+/// occurrences of each field are combined: every occurrence is parsed into an
+/// [Item](FieldCollector::Item) and pushed into the [Builder](FieldCollector::Builder), which is
+/// finally converted into the field value. Fields whose type does not implement this trait are
+/// treated as required single values.
+///
+/// Implementations are provided for [Option], [Vec], [VecDeque], [HashSet], [BTreeSet], arrays
+/// and [Presence](crate::Presence).
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::{FieldCollector, TryFromMultipart};
+///
+/// /// A list that must contain at least one item.
+/// struct NonEmpty<T>(Vec<T>);
+///
+/// impl<T> FieldCollector for NonEmpty<T> {
+///     type Item = T;
+///     type Builder = Vec<T>;
+///
+///     fn init() -> Self::Builder {
+///         Vec::new()
+///     }
+///
+///     fn push(builder: &mut Self::Builder, item: Self::Item) {
+///         builder.push(item);
+///     }
+///
+///     fn finish(builder: Self::Builder) -> Option<Self> {
+///         (!builder.is_empty()).then_some(NonEmpty(builder))
+///     }
+/// }
+///
+/// #[derive(TryFromMultipart)]
+/// struct Data {
+///     tags: NonEmpty<String>, // reported as missing if no `tags` field is sent
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot collect multiple occurrences of a field",
+    note = "implement `FieldCollector` for `{Self}` or use a collection such as `Vec`"
+)]
+pub trait FieldCollector: Sized {
+    /// Type of a single occurrence of the field.
+    type Item;
+
+    /// Intermediate state used while the request is being parsed.
+    type Builder;
+
+    /// Whether the field accepts multiple occurrences.
+    ///
+    /// When `false` each [push](FieldCollector::push) should replace the previous value, and the
+    /// `duplicates` policy of the derive macro is applied. Multi-valued fields also accept the
+    /// `name[]` syntax.
+    const MULTIPLE: bool = true;
+
+    /// Exact number of occurrences the field must have, if any.
+    ///
+    /// When set, occurrences exceeding the count are not parsed and the derive macro returns a
+    /// [WrongFieldCount](crate::TypedMultipartError::WrongFieldCount) error if a different number
+    /// of occurrences is found.
+    const COUNT: Option<usize> = None;
+
+    /// Create the initial state, before any occurrence of the field is found.
+    fn init() -> Self::Builder;
+
+    /// Add an occurrence of the field.
+    fn push(builder: &mut Self::Builder, item: Self::Item);
+
+    /// Create the field value, returning [None] if the field must be reported as missing.
+    fn finish(builder: Self::Builder) -> Option<Self>;
+}
+
+impl<T> FieldCollector for Option<T> {
+    type Item = T;
+    type Builder = Self;
+
+    const MULTIPLE: bool = false;
+
+    fn init() -> Self::Builder {
+        None
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        *builder = Some(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+impl<T> FieldCollector for Vec<T> {
+    type Item = T;
+    type Builder = Self;
+
+    fn init() -> Self::Builder {
+        Vec::new()
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.push(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+impl<T> FieldCollector for VecDeque<T> {
+    type Item = T;
+    type Builder = Self;
+
+    fn init() -> Self::Builder {
+        VecDeque::new()
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.push_back(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+impl<T> FieldCollector for HashSet<T>
+where
+    T: Eq + Hash,
+{
+    type Item = T;
+    type Builder = Self;
+
+    fn init() -> Self::Builder {
+        HashSet::new()
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.insert(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+impl<T> FieldCollector for BTreeSet<T>
+where
+    T: Ord,
+{
+    type Item = T;
+    type Builder = Self;
+
+    fn init() -> Self::Builder {
+        BTreeSet::new()
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.insert(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+impl<T, const N: usize> FieldCollector for [T; N] {
+    type Item = T;
+    type Builder = Vec<T>;
+
+    const COUNT: Option<usize> = Some(N);
+
+    fn init() -> Self::Builder {
+        Vec::with_capacity(N)
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        builder.push(item);
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        builder.try_into().ok()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+
+    fn collect<T: FieldCollector>(items: impl IntoIterator<Item = T::Item>) -> Option<T> {
+        let mut builder = T::init();
+        for item in items {
+            T::push(&mut builder, item);
+        }
+        T::finish(builder)
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(collect::<Option<u8>>([]), Some(None));
+        assert_eq!(collect::<Option<u8>>([1, 2]), Some(Some(2)));
+    }
+
+    #[test]
+    fn test_collections() {
+        assert_eq!(collect::<Vec<u8>>([2, 1, 2]), Some(vec![2, 1, 2]));
+        assert_eq!(collect::<VecDeque<u8>>([2, 1, 2]), Some(VecDeque::from([2, 1, 2])));
+        assert_eq!(collect::<HashSet<u8>>([2, 1, 2]), Some(HashSet::from([1, 2])));
+        assert_eq!(collect::<BTreeSet<u8>>([2, 1, 2]), Some(BTreeSet::from([1, 2])));
+        assert_eq!(collect::<[u8; 3]>([2, 1, 2]), Some([2, 1, 2]));
+        assert_eq!(collect::<[u8; 3]>([2, 1]), None);
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{TryFromFieldWithState, TypedMultipartError};
-use async_trait::async_trait;
+use crate::{SafeFileName, TryFromFieldWithState, TypedMultipartError};
 use axum::extract::multipart::Field;
 use axum::http::HeaderMap;
+use std::sync::Arc;
 
 /// Additional information about the file supplied by the client in the request.
+///
+/// New fields may be added in future versions, so outside of this crate the struct can only be
+/// created through [Default] or from a [Field].
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct FieldMetadata {
     /// Name of the HTML field in the form.
     ///
@@ -22,7 +26,8 @@ pub struct FieldMetadata {
     ///
     /// The filename is always optional and must not be used blindly by the
     /// application: path information should be stripped, and conversion to the
-    /// server file system rules should be done.
+    /// server file system rules should be done. Use
+    /// [safe_file_name](Self::safe_file_name) to get a sanitized version.
     ///
     /// Extracted from the
     /// [`Content-Disposition`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Disposition)
@@ -37,7 +42,38 @@ pub struct FieldMetadata {
     pub content_type: Option<String>,
 
     /// HTTP headers sent with the field.
-    pub headers: HeaderMap,
+    ///
+    /// Shared between the copies of the metadata created while parsing the field.
+    pub headers: Arc<HeaderMap>,
+
+    /// Maximum size of the field contents in bytes, if any.
+    ///
+    /// Not sent by the client: set from the `limit` parameter while the field is parsed, so
+    /// [TryFromChunks](crate::TryFromChunks) implementations can size their buffers.
+    pub limit_bytes: Option<usize>,
+
+    /// Media type detected from the first bytes of the field contents.
+    ///
+    /// Only set for fields declared with `#[form_data(sniff)]`. Requires the `infer_0_19`
+    /// feature.
+    #[cfg(feature = "infer_0_19")]
+    pub sniffed_content_type: Option<crate::SniffedContentType>,
+
+    /// Store the contents are copied to while the field is read, see
+    /// [FormOutcome](crate::FormOutcome).
+    pub(crate) recorder: Option<crate::form_outcome::ValueRecorder>,
+}
+
+impl FieldMetadata {
+    /// Returns the [file_name](Self::file_name) sanitized so that it can be joined to a
+    /// directory, or [None] if it is absent or cannot be made safe.
+    ///
+    /// The RFC 5987 `filename*` parameter of the `Content-Disposition` header takes precedence
+    /// over the plain `filename` parameter when present.
+    pub fn safe_file_name(&self) -> Option<SafeFileName> {
+        let extended = SafeFileName::extended_file_name(&self.headers);
+        SafeFileName::new(extended.as_deref().or(self.file_name.as_deref())?)
+    }
 }
 
 impl From<&Field<'_>> for FieldMetadata {
@@ -46,7 +82,11 @@ impl From<&Field<'_>> for FieldMetadata {
             name: field.name().map(String::from),
             file_name: field.file_name().map(String::from),
             content_type: field.content_type().map(String::from),
-            headers: field.headers().clone(),
+            headers: Arc::new(field.headers().clone()),
+            limit_bytes: None,
+            #[cfg(feature = "infer_0_19")]
+            sniffed_content_type: None,
+            recorder: None,
         }
     }
 }
@@ -74,7 +114,6 @@ pub struct FieldData<T> {
     pub contents: T,
 }
 
-#[async_trait]
 impl<S, T> TryFromFieldWithState<S> for FieldData<T>
 where
     S: Sync,
@@ -85,8 +124,10 @@ where
         limit_bytes: Option<usize>,
         state: &S,
     ) -> Result<Self, TypedMultipartError> {
-        let metadata = FieldMetadata::from(&field);
-        let contents = T::try_from_field_with_state(field, limit_bytes, state).await?;
+        let metadata = FieldMetadata { limit_bytes, ..FieldMetadata::from(&field) };
+        let contents =
+            T::try_from_field_with_state_and_metadata(field, metadata.clone(), limit_bytes, state)
+                .await?;
         Ok(Self { metadata, contents })
     }
 }
@@ -132,4 +173,22 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[test]
+    fn test_safe_file_name() {
+        let metadata =
+            FieldMetadata { file_name: Some(String::from("../rates.txt")), ..Default::default() };
+        assert_eq!(metadata.safe_file_name().unwrap().as_str(), "rates.txt");
+
+        let headers = HeaderMap::from_iter([(
+            axum::http::header::CONTENT_DISPOSITION,
+            "form-data; name=\"file\"; filename=\"rates.txt\"; filename*=UTF-8''%e2%82%ac%20rates.txt"
+                .parse()
+                .unwrap(),
+        )]);
+        let metadata = FieldMetadata { headers: Arc::new(headers), ..metadata };
+        assert_eq!(metadata.safe_file_name().unwrap().as_str(), "€ rates.txt");
+
+        assert!(FieldMetadata::default().safe_file_name().is_none());
+    }
 }
@@ -0,0 +1,174 @@
+use crate::__private::FieldErrors;
+use crate::{TryFromMultipartWithState, TypedMultipartError};
+use axum::extract::{FromRequest, Multipart, Request};
+use bytes::BytesMut;
+use std::collections::HashMap;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// Maximum size of a recorded value, larger values are not echoed back.
+const MAX_VALUE_BYTES: usize = 64 * 1024;
+
+/// Maximum size of all the values recorded for a request.
+const MAX_TOTAL_BYTES: usize = 1024 * 1024;
+
+/// Extractor for server-rendered forms that must be displayed again when the submission is
+/// rejected.
+///
+/// Resolves to [FormOutcome::Valid] when `T` can be parsed from the request, otherwise to
+/// [FormOutcome::Invalid] holding the raw values sent by the user and the errors that caused the
+/// rejection. When `T` is derived with [TryFromMultipart](crate::TryFromMultipart) the parsing
+/// goes on after an invalid field, so the errors of all the fields are reported at once. Errors
+/// not related to a specific field, such as a malformed request body, are still returned as a
+/// [TypedMultipartError] rejection.
+///
+/// The raw values are copied while `T` reads the request, so only the textual fields parsed by
+/// `T` are available. Values larger than 64 KiB are not stored, and neither are the values
+/// received once 1 MiB of values has been stored.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum::response::Html;
+/// use axum_typed_multipart::{FormOutcome, TryFromMultipart};
+///
+/// #[derive(TryFromMultipart)]
+/// struct SignUp {
+///     email: String,
+///     age: u8,
+/// }
+///
+/// async fn sign_up(form: FormOutcome<SignUp>) -> Html<String> {
+///     match form {
+///         FormOutcome::Valid(data) => Html(format!("Welcome, {}!", data.email)),
+///         FormOutcome::Invalid(form) => {
+///             let email = form.value("email").unwrap_or_default();
+///             let error = form.error("age").map(ToString::to_string).unwrap_or_default();
+///             Html(format!(r#"<input name="email" value="{email}"> <p>{error}</p>"#))
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub enum FormOutcome<T> {
+    /// The form was parsed successfully.
+    Valid(T),
+
+    /// The form was rejected.
+    Invalid(InvalidForm),
+}
+
+/// Rejected form submission, see [FormOutcome].
+#[derive(Debug, Default)]
+pub struct InvalidForm {
+    values: Vec<(String, String)>,
+    errors: HashMap<String, TypedMultipartError>,
+}
+
+impl InvalidForm {
+    /// Returns the raw value of the last occurrence of the field with the supplied name.
+    ///
+    /// Only textual fields are available, file uploads and values that are not valid UTF-8 or
+    /// exceed the size limits of [FormOutcome] are never stored.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.iter().rev().find(|(n, _)| n == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the raw values of all the occurrences of the field with the supplied name.
+    pub fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.values.iter().filter(move |(n, _)| n == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first error raised for the field with the supplied name.
+    pub fn error(&self, name: &str) -> Option<&TypedMultipartError> {
+        self.errors.get(name)
+    }
+
+    /// Returns the errors that caused the rejection, indexed by field name.
+    ///
+    /// Types implementing [TryFromMultipartWithState] by hand only report their first error.
+    pub fn errors(&self) -> &HashMap<String, TypedMultipartError> {
+        &self.errors
+    }
+}
+
+/// Values recorded for a request, see [ValueRecorder].
+#[derive(Debug, Default)]
+struct RecordedValues {
+    values: Vec<(String, String)>,
+    size_bytes: usize,
+}
+
+/// Store of the raw textual values of a request, shared with the fields while they are parsed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ValueRecorder(Arc<Mutex<RecordedValues>>);
+
+impl ValueRecorder {
+    /// Start recording the contents of a field.
+    pub(crate) fn field(&self) -> RecordedField {
+        RecordedField { recorder: self.clone(), contents: Some(BytesMut::new()) }
+    }
+
+    /// Take the values recorded so far, in the order they were received.
+    pub(crate) fn take(&self) -> Vec<(String, String)> {
+        mem::take(&mut self.0.lock().unwrap().values)
+    }
+}
+
+/// Contents of a field being recorded, see [ValueRecorder].
+pub(crate) struct RecordedField {
+    recorder: ValueRecorder,
+    contents: Option<BytesMut>,
+}
+
+impl RecordedField {
+    /// Append a chunk of the field, giving up once the value is too large to be echoed back.
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        if let Some(contents) = &mut self.contents {
+            if contents.len() + chunk.len() > MAX_VALUE_BYTES {
+                self.contents = None;
+            } else {
+                contents.extend_from_slice(chunk);
+            }
+        }
+    }
+
+    /// Store the value once the whole field has been read.
+    pub(crate) fn finish(&mut self, name: &str) {
+        let Some(contents) = self.contents.take() else { return };
+        let Ok(value) = String::from_utf8(contents.into()) else { return };
+
+        let mut recorded = self.recorder.0.lock().unwrap();
+        if recorded.size_bytes + value.len() <= MAX_TOTAL_BYTES {
+            recorded.size_bytes += value.len();
+            recorded.values.push((name.to_owned(), value));
+        }
+    }
+}
+
+impl<S, T> FromRequest<S> for FormOutcome<T>
+where
+    S: Send + Sync,
+    T: TryFromMultipartWithState<S>,
+{
+    type Rejection = TypedMultipartError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = &mut Multipart::from_request(req, state).await?;
+
+        let mut errors = FieldErrors::collecting();
+        match T::try_from_multipart_with_state_and_errors(multipart, state, &mut errors).await? {
+            Some(data) => Ok(Self::Valid(data)),
+            None => {
+                let values = errors.values();
+                let mut by_field = HashMap::new();
+                for err in errors {
+                    if let Some(field_name) = err.field_name() {
+                        by_field.entry(field_name.to_owned()).or_insert(err);
+                    }
+                }
+                Ok(Self::Invalid(InvalidForm { values, errors: by_field }))
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{FieldMetadata, TryFromChunks, TypedMultipartError};
+use axum::body::Bytes;
+use futures_util::stream;
+use std::collections::HashMap;
+
+/// Runtime description of a form, used to validate a
+/// [DynamicMultipart](crate::DynamicMultipart).
+///
+/// Validation produces the same [TypedMultipartError] variants as the
+/// [TryFromMultipart](crate::TryFromMultipart) derive macro:
+/// - [MissingField](TypedMultipartError::MissingField) if a required field is not present.
+/// - [WrongFieldType](TypedMultipartError::WrongFieldType) if a field does not match its
+///   [FieldKind].
+/// - [FieldTooLarge](TypedMultipartError::FieldTooLarge) if a field exceeds its size limit.
+///
+/// Like the derive macro, fields not described by the schema are ignored and only the last
+/// occurrence of fields that don't allow [multiple](FieldSchema::multiple) values is kept. When
+/// [strict](FormSchema::strict) mode is enabled
+/// [UnknownField](TypedMultipartError::UnknownField),
+/// [DuplicateField](TypedMultipartError::DuplicateField) and
+/// [NamelessField](TypedMultipartError::NamelessField) errors are returned instead.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::{FieldKind, FieldSchema, FormSchema};
+///
+/// let schema = FormSchema::new()
+///     .field(FieldSchema::new("title").kind(FieldKind::Text).required(true))
+///     .field(FieldSchema::new("age").kind(FieldKind::Integer))
+///     .field(FieldSchema::new("attachments").multiple(true).limit_bytes(Some(1024 * 1024)))
+///     .strict(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormSchema {
+    pub(crate) fields: Vec<FieldSchema>,
+    /// Position in `fields` of the description of each name.
+    indices: HashMap<String, usize>,
+    pub(crate) strict: bool,
+}
+
+impl FormSchema {
+    /// Creates an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a field to the schema, replacing any previous field with the same name.
+    pub fn field(mut self, field: FieldSchema) -> Self {
+        match self.indices.get(&field.name) {
+            Some(&index) => self.fields[index] = field,
+            None => {
+                self.indices.insert(field.name.clone(), self.fields.len());
+                self.fields.push(field);
+            }
+        }
+        self
+    }
+
+    /// Enables or disables strict mode.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the description of the field with the supplied name, if any.
+    pub fn get(&self, name: &str) -> Option<&FieldSchema> {
+        self.indices.get(name).map(|&index| &self.fields[index])
+    }
+}
+
+/// Runtime description of a single field of a [FormSchema].
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    pub(crate) name: String,
+    pub(crate) kind: FieldKind,
+    pub(crate) required: bool,
+    pub(crate) multiple: bool,
+    pub(crate) limit_bytes: Option<usize>,
+}
+
+impl FieldSchema {
+    /// Creates an optional, single valued field of kind [FieldKind::Bytes] without size limits.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: FieldKind::Bytes,
+            required: false,
+            multiple: false,
+            limit_bytes: None,
+        }
+    }
+
+    /// Sets the expected kind of the field contents.
+    pub fn kind(mut self, kind: FieldKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets whether the field must be present in the request.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Sets whether the field can be present multiple times in the request.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Sets the size limit for each occurrence of the field, [None] meaning unlimited.
+    pub fn limit_bytes(mut self, limit_bytes: Option<usize>) -> Self {
+        self.limit_bytes = limit_bytes;
+        self
+    }
+
+    /// Name of the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Expected kind of the contents of a [FieldSchema].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Arbitrary binary data.
+    #[default]
+    Bytes,
+
+    /// Valid UTF-8 text.
+    Text,
+
+    /// Text that can be parsed as an [i64].
+    Integer,
+
+    /// Text that can be parsed as an [f64].
+    Float,
+
+    /// Text that can be parsed as a [bool].
+    Boolean,
+}
+
+impl FieldKind {
+    /// Checks that the field contents match the expected kind.
+    pub(crate) async fn validate(
+        self,
+        bytes: &Bytes,
+        metadata: &FieldMetadata,
+    ) -> Result<(), TypedMultipartError> {
+        async fn parse<T: TryFromChunks>(
+            bytes: &Bytes,
+            metadata: &FieldMetadata,
+        ) -> Result<(), TypedMultipartError> {
+            let chunks = stream::iter([Ok(bytes.clone())]);
+            T::try_from_chunks(chunks, metadata.clone()).await.map(|_| ())
+        }
+
+        match self {
+            Self::Bytes => Ok(()),
+            Self::Text => parse::<String>(bytes, metadata).await,
+            Self::Integer => parse::<i64>(bytes, metadata).await,
+            Self::Float => parse::<f64>(bytes, metadata).await,
+            Self::Boolean => parse::<bool>(bytes, metadata).await,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_field_kind_validate() {
+        let metadata = FieldMetadata { name: Some("data".into()), ..Default::default() };
+        let cases = [
+            (FieldKind::Bytes, Bytes::from_static(&[0x80]), true),
+            (FieldKind::Text, Bytes::from_static(b"text"), true),
+            (FieldKind::Text, Bytes::from_static(&[0x80]), false),
+            (FieldKind::Integer, Bytes::from_static(b"-42"), true),
+            (FieldKind::Integer, Bytes::from_static(b"4.2"), false),
+            (FieldKind::Float, Bytes::from_static(b"4.2"), true),
+            (FieldKind::Float, Bytes::from_static(b"text"), false),
+            (FieldKind::Boolean, Bytes::from_static(b"on"), true),
+            (FieldKind::Boolean, Bytes::from_static(b"text"), false),
+        ];
+
+        for (kind, bytes, valid) in cases {
+            let res = kind.validate(&bytes, &metadata).await;
+            assert_eq!(res.is_ok(), valid, "{kind:?} {bytes:?}");
+            if !valid {
+                assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+            }
+        }
+    }
+
+    #[test]
+    fn test_form_schema() {
+        let schema = FormSchema::new()
+            .field(FieldSchema::new("name").kind(FieldKind::Text).required(true))
+            .strict(true);
+
+        assert!(schema.strict);
+        assert!(schema.get("unknown").is_none());
+
+        let field = schema.get("name").unwrap();
+        assert_eq!(field.name(), "name");
+        assert_eq!(field.kind, FieldKind::Text);
+        assert!(field.required);
+        assert!(!field.multiple);
+        assert_eq!(field.limit_bytes, None);
+
+        let schema = schema.field(FieldSchema::new("name").multiple(true));
+        assert_eq!(schema.fields.len(), 1);
+        assert!(schema.get("name").unwrap().multiple);
+    }
+}
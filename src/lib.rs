@@ -8,10 +8,24 @@
 //!
 //! ### Features
 //!
-//! All features are enabled by default.
+//! The `chrono_0_4`, `rust_decimal_1`, `tempfile_3` and `uuid_1` features are enabled by default,
+//! the other integrations must be enabled explicitly.
 //!
 //! - `chrono_0_4`: Enables support for [chrono::DateTime](chrono_0_4::DateTime) (v0.4)
+//! - `ciborium_0_2`: Enables the [Cbor] field type for parts containing CBOR values (v0.2)
+//! - `infer_0_19`: Enables the detection of the content type of the fields from their first bytes
+//!   using [infer](infer_0_19) (v0.19)
+//! - `rmp_serde_1`: Enables the [MessagePack] field type for parts containing MessagePack values
+//!   (v1)
+//! - `serde`: Enables the [SerdeMultipart] extractor and the [MultipartDeserializer] for types
+//!   implementing [serde::Deserialize]
+//! - `serde_json`: Enables the [Json] field type for parts containing JSON documents
+//! - `serde_norway_0_9`: Enables the [Yaml] field type for parts containing YAML documents, using
+//!   the maintained [serde_norway](serde_norway_0_9) fork of `serde_yaml` (v0.9)
 //! - `tempfile_3`: Enables support for [tempfile::NamedTempFile](tempfile_3::NamedTempFile) (v3)
+//! - `toml_1`: Enables the [Toml] field type for parts containing TOML documents (v1)
+//! - `tokio_1`: Enables the [TryFromAsyncRead] and [TryFromBytesBlocking] traits for parsers based
+//!   on [tokio::io::AsyncBufRead] or running on blocking threads (v1)
 //! - `uuid_1`: Enables support for [uuid::Uuid](uuid_1::Uuid) (v1)
 //! - `rust_decimal_1`: Enables support for [rust_decimal::Decimal](rust_decimal_1::Decimal) (v1)
 //!
@@ -35,11 +49,14 @@
 //! - [char]
 //! - [String]
 //! - [axum::body::Bytes]
+//! - `Vec<u8>`, `Box<[u8]>` and `Arc<[u8]>` (raw bytes, not a list of [u8] fields)
 //! - [chrono::DateTime](chrono_0_4::DateTime) (feature: `chrono_0_4`)
 //! - [chrono::NaiveDate](chrono_0_4::NaiveDate) (feature: `chrono_0_4`)
 //! - [tempfile::NamedTempFile](tempfile_3::NamedTempFile) (feature: `tempfile_3`)
 //! - [uuid::Uuid](uuid_1::Uuid) (feature: `uuid_1`)
 //! - [rust_decimal::Decimal](rust_decimal_1::Decimal) (feature: `rust_decimal_1`)
+//! - `Result<T, E>`, capturing parsing errors (see
+//!   [Capturing field errors](#capturing-field-errors))
 //!
 //! If the request body is malformed the request will be aborted with an error.
 //!
@@ -63,6 +80,20 @@
 //! }
 //! ```
 //!
+//! ### Partial updates
+//!
+//! When it's necessary to distinguish between a field that was not sent and a field that was sent
+//! empty (e.g. to clear a value in a `PATCH` endpoint) the field can be declared as a
+//! [Presence](crate::Presence). Like [Option], the field will never be reported as missing.
+//! ```rust
+//! use axum_typed_multipart::{Presence, TryFromMultipart};
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     nickname: Presence<String>, // `Absent`, `Empty` or `Value("...")`
+//! }
+//! ```
+//!
 //! ### Renaming fields
 //!
 //! If you would like to assign a custom name for the source field you can use the `field_name`
@@ -102,6 +133,25 @@
 //! NOTE: If the `#[form_data(field_name = "...")]` attribute is specified, the `rename_all` rule
 //! will not be applied.
 //!
+//! ### Tuple structs
+//!
+//! Tuple structs are supported as well. Since their fields have no name, each one must either
+//! specify the `field_name` parameter or be named after its position (`0`, `1`, ...) using the
+//! `positional` parameter of the `try_from_multipart` attribute.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct Login(
+//!     #[form_data(field_name = "user")] String,
+//!     #[form_data(field_name = "pass")] String,
+//! );
+//!
+//! #[derive(TryFromMultipart)]
+//! #[try_from_multipart(positional)]
+//! struct Point(f64, f64); // read from the `0` and `1` fields
+//! ```
+//!
 //! ### Default values
 //!
 //! If the `default` parameter in the `form_data` attribute is present the value will be populated
@@ -116,6 +166,54 @@
 //! }
 //! ```
 //!
+//! ### Normalization
+//!
+//! The `trim`, `lowercase` and `nfc` parameters of the `form_data` attribute can be used to
+//! respectively strip leading and trailing whitespace, convert to lowercase and apply Unicode
+//! [NFC](https://unicode.org/reports/tr15/) normalization to the textual representation of the
+//! field before it is parsed. This requires the field type to implement
+//! [TryFromChunks](crate::TryFromChunks) and the field contents to be valid UTF-8.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     #[form_data(trim)]
+//!     age: u32, // " 42 " is parsed as 42
+//!     #[form_data(trim, lowercase, nfc)]
+//!     email: String,
+//! }
+//! ```
+//!
+//! ### Checkboxes
+//!
+//! Browsers do not send unchecked checkboxes at all, so a plain [bool] field would be reported as
+//! missing. Using the `checkbox` parameter of the `form_data` attribute the field will be set to
+//! `false` when absent and to `true` when present with any value except for an explicit false
+//! literal like `off` or `0`.
+//!
+//! The parameter can also be applied to [Vec] fields (or any other [FieldCollector]) to handle
+//! groups of checkboxes sharing the same name. In that case empty values, commonly sent by hidden
+//! inputs to make sure the group is always present in the request, are skipped. Since an absent
+//! checkbox is unchecked rather than unknown, the parameter is rejected on [Option] fields.
+//! ```rust
+//! use axum_typed_multipart::{TryFromField, TryFromMultipart};
+//!
+//! #[derive(TryFromField)]
+//! enum Topping {
+//!     Cheese,
+//!     Ham,
+//! }
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     #[form_data(checkbox)]
+//!     subscribe: bool,
+//!     #[form_data(checkbox)]
+//!     toppings: Vec<Topping>,
+//! }
+//! ```
+//!
 //! ### Field metadata
 //!
 //! If you need access to the field metadata (e.g. the field headers like file name or content
@@ -130,6 +228,10 @@
 //! }
 //! ```
 //!
+//! The file name sent by the client must not be joined to a path as-is: use
+//! [safe_file_name](FieldMetadata::safe_file_name) to get a [SafeFileName](SafeFileName) with
+//! the directories, control characters and reserved names removed.
+//!
 //! ### Field size limits
 //!
 //! By default, there are no size limits on individual fields. You can set a limit using the
@@ -147,6 +249,48 @@
 //! }
 //! ```
 //!
+//! ### Content types
+//!
+//! The media types accepted for a field can be restricted using the `content_type` parameter,
+//! which can be repeated and accepts wildcards like `"image/*"`. The `Content-Type` of the part
+//! is checked before its contents are read, returning an
+//! [InvalidContentType](TypedMultipartError::InvalidContentType) error (`415 Unsupported Media
+//! Type`) if it does not match. Parts without a `Content-Type` are treated as `text/plain`.
+//! ```rust
+//! use axum::body::Bytes;
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     #[form_data(content_type = "image/png", content_type = "image/jpeg")]
+//!     avatar: Bytes,
+//!     #[form_data(content_type = "image/*")]
+//!     photos: Vec<Bytes>,
+//! }
+//! ```
+//!
+//! #### Content sniffing
+//!
+//! The `Content-Type` is declared by the client and cannot be trusted. With the `infer_0_19`
+//! feature the `sniff` parameter detects the media type from the first bytes of the contents,
+//! and rejects the field before it is parsed if the detected type does not match the
+//! `content_type` patterns or, when no patterns are set, the declared `Content-Type`. Empty
+//! contents are only accepted in the latter case. The detected type is exposed in
+//! [FieldMetadata::sniffed_content_type]. The contents are read through [TryFromChunks], so
+//! `sniff` requires a type implementing it, optionally wrapped in [FieldData].
+//! ```rust
+//! use axum::body::Bytes;
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     #[form_data(sniff, content_type = "image/png", content_type = "image/jpeg")]
+//!     avatar: Bytes,
+//!     #[form_data(sniff)]
+//!     document: Bytes,
+//! }
+//! ```
+//!
 //! ### Large uploads
 //!
 //! For large uploads you can save the contents of the field to the file system using
@@ -181,6 +325,75 @@
 //! }
 //! ```
 //!
+//! Some clients send lists as a single field with delimiter-separated values (e.g. `a,b,c`)
+//! instead. The `delimiter` parameter of the `form_data` attribute will split the contents of
+//! each occurrence of a [Vec] field and parse every piece separately, skipping empty pieces.
+//! Repeated fields are still supported, and parsing errors will report the index of the invalid
+//! item (e.g. `tags[2]`).
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     #[form_data(delimiter = ",", trim)]
+//!     tags: Vec<String>, // "a, b" is parsed as ["a", "b"]
+//! }
+//! ```
+//!
+//! #### Bracket syntax
+//!
+//! Field names using the PHP/Rails-style bracket syntax are supported as well: fields named
+//! `items[]` are collected into the `items` [Vec] field, while fields named `meta[key]` are
+//! collected into the `meta` field if it is declared as a
+//! [HashMap](std::collections::HashMap) or [BTreeMap](std::collections::BTreeMap) with [String]
+//! keys, possibly through a type alias. Field names with malformed or nested brackets are treated
+//! as unknown fields.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//! use std::collections::HashMap;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     items: Vec<String>,            // items[]=a&items[]=b
+//!     meta: HashMap<String, String>, // meta[color]=red&meta[size]=xl
+//! }
+//! ```
+//!
+//! #### Custom collections
+//!
+//! Besides [Vec], fields can be declared using any type implementing the [FieldCollector] trait,
+//! such as [VecDeque](std::collections::VecDeque), [HashSet](std::collections::HashSet) or
+//! [BTreeSet](std::collections::BTreeSet). The trait is resolved by the compiler, so type aliases
+//! and custom collections work as well. Fields whose type does not implement [FieldCollector] are
+//! treated as required single values.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//! use std::collections::BTreeSet;
+//!
+//! type Tags = BTreeSet<String>;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     tags: Tags, // sorted and deduplicated
+//! }
+//! ```
+//!
+//! #### Fixed-size arrays
+//!
+//! Arrays such as `[T; N]` require the field to be present exactly `N` times, otherwise a
+//! [WrongFieldCount](TypedMultipartError::WrongFieldCount) error is returned. Occurrences past the
+//! expected count are not parsed.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     answers: [u8; 3],
+//!     #[form_data(delimiter = ",")]
+//!     coordinates: [f64; 2], // e.g. "45.46,9.19"
+//! }
+//! ```
+//!
 //! ### Strict mode
 //!
 //! By default, the derive macro will store the last occurrence of a field, and it will ignore
@@ -198,6 +411,86 @@
 //! }
 //! ```
 //!
+//! The behaviors enabled by `strict` can also be selected individually using the
+//! `deny_unknown_fields`, `deny_duplicates` and `deny_nameless` parameters. The `duplicates`
+//! parameter controls which occurrence of a repeated field is kept: `"first"`, `"last"` (the
+//! default) or `"error"`, which is equivalent to `deny_duplicates`.
+//! ```rust
+//! use axum_typed_multipart::TryFromMultipart;
+//!
+//! #[derive(TryFromMultipart)]
+//! #[try_from_multipart(deny_unknown_fields, duplicates = "first")]
+//! struct RequestData {
+//!     name: String,
+//! }
+//! ```
+//!
+//! ### Unknown fields
+//!
+//! To keep the fields that are not matched by any other field of the struct, mark a field with
+//! the `rest` parameter of the `form_data` attribute. The field can be declared either as a [Vec]
+//! or as a map of field names to [Vec]s, and it will receive every unmatched field with a name,
+//! subject to its own size limit. When a `rest` field is present, unknown fields are accepted
+//! even in strict mode.
+//! ```rust
+//! use axum::body::Bytes;
+//! use axum_typed_multipart::{FieldData, TryFromMultipart};
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     name: String,
+//!     #[form_data(rest, limit = "1MiB")]
+//!     extra: Vec<FieldData<Bytes>>,
+//! }
+//! ```
+//!
+//! ### Dynamic forms
+//!
+//! When the shape of the form is only known at runtime (e.g. forms defined by users of a CMS),
+//! use the [DynamicMultipart] extractor. It collects all the named fields in the order they were
+//! received and allows parsing them on demand into any type implementing [TryFromChunks].
+//!
+//! A [FormSchema] can be used to describe required fields, field kinds and size limits. It
+//! produces the same errors as the derive macro.
+//! ```rust
+//! use axum::extract::Multipart;
+//! use axum_typed_multipart::{FieldKind, FieldSchema, FormSchema};
+//! use axum_typed_multipart::{DynamicMultipart, TypedMultipartError};
+//!
+//! async fn handle_data(mut multipart: Multipart) -> Result<(), TypedMultipartError> {
+//!     let schema = FormSchema::new()
+//!         .field(FieldSchema::new("title").kind(FieldKind::Text).required(true))
+//!         .field(FieldSchema::new("attachments").multiple(true).limit_bytes(Some(1024)))
+//!         .strict(true);
+//!
+//!     let form = DynamicMultipart::from_multipart_with_schema(&mut multipart, &schema).await?;
+//!     let title: String = form.get("title").await?;
+//!     println!("title: {title}, attachments: {}", form.fields_named("attachments").count());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Serde models
+//!
+//! With the `serde` feature, types implementing [serde::Deserialize] can be extracted directly
+//! using [SerdeMultipart], without deriving [TryFromMultipart](crate::TryFromMultipart). Fields
+//! sent more than once are deserialized as sequences and values are parsed from their textual
+//! representation, see [MultipartDeserializer] for the details.
+//! ```rust
+//! use axum_typed_multipart::SerdeMultipart;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct RequestData {
+//!     name: String,
+//!     tags: Vec<String>,
+//! }
+//!
+//! async fn handle_data(SerdeMultipart(data): SerdeMultipart<RequestData>) {
+//!     println!("name: {}, tags: {}", data.name, data.tags.join(", "));
+//! }
+//! ```
+//!
 //! ### Enums
 //!
 //! `axum_typed_multipart` also supports custom enum parsing by deriving the
@@ -244,9 +537,84 @@
 //! [TryFromChunks](TryFromChunks) trait, which will automatically provide a [TryFromField](crate::TryFromField)
 //! implementation with proper size limit handling.
 //!
+//! Parsers that consume a reader instead of a stream can implement the `TryFromAsyncRead` trait,
+//! available with the `tokio_1` feature, which in turn provides the
+//! [TryFromChunks](TryFromChunks) implementation. CPU-heavy parsers can implement the
+//! `TryFromBytesBlocking` trait instead, so they are run on a dedicated thread without blocking
+//! the async runtime.
+//!
 //! To implement the [TryFromChunks](TryFromChunks) trait for external types you will need
 //! to create a newtype wrapper and implement the trait for the wrapper.
 //!
+//! The traits use native `async fn` support, so the implementations are written as plain
+//! `async fn` methods without any additional attribute. The returned futures must be [Send].
+//!
+//! ### Structured fields
+//!
+//! Parts containing structured data, such as the metadata sent next to a file, can be
+//! deserialized with the [Json], [Toml], [Yaml], [MessagePack] and [Cbor] field types, each
+//! enabled by the feature of the underlying crate. The [Structured] field type accepts any of the
+//! enabled formats, selecting it through the `Content-Type` of the part.
+//! ```rust
+//! use axum::body::Bytes;
+//! use axum_typed_multipart::{Json, TryFromMultipart};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Metadata {
+//!     title: String,
+//! }
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     metadata: Json<Metadata>,
+//!     file: Bytes,
+//! }
+//! ```
+//!
+//! ### Capturing field errors
+//!
+//! Fields declared as `Result<T, E>` store the error raised while parsing them instead of
+//! rejecting the whole request, which is useful to re-render a form with inline validation
+//! messages next to the values that were valid. This includes the errors raised by the
+//! `#[form_data(...)]` parameters, such as a `Content-Type` rejected by `content_type` or a
+//! field exceeding its `limit`. Missing fields are captured as a
+//! [MissingField](TypedMultipartError::MissingField) error, while errors caused by a malformed
+//! request body still abort the request. The error type must implement
+//! `From<TypedMultipartError>`.
+//! ```rust
+//! use axum_typed_multipart::{TryFromMultipart, TypedMultipartError};
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     name: String,
+//!     age: Result<u32, TypedMultipartError>,
+//!     tags: Vec<Result<u8, TypedMultipartError>>, // one result per occurrence
+//! }
+//! ```
+//!
+//! ### Re-rendering forms
+//!
+//! Server-rendered applications usually need to display a rejected form again, together with the
+//! values entered by the user and the error messages. Using [FormOutcome] as an extractor, a
+//! rejected submission resolves to an [InvalidForm] holding the raw values of the textual fields
+//! read while parsing the form and the errors of all the invalid fields, indexed by field name.
+//! ```rust
+//! use axum_typed_multipart::{FormOutcome, TryFromMultipart};
+//!
+//! #[derive(TryFromMultipart)]
+//! struct RequestData {
+//!     name: String,
+//! }
+//!
+//! async fn handler(form: FormOutcome<RequestData>) -> String {
+//!     match form {
+//!         FormOutcome::Valid(data) => format!("Hello, {}!", data.name),
+//!         FormOutcome::Invalid(form) => format!("{:?}", form.error("name")),
+//!     }
+//! }
+//! ```
+//!
 //! ### Custom error format
 //!
 //! When using [TypedMultipart](TypedMultipart) as an argument for your handlers, errors are
@@ -272,8 +640,9 @@
 //! generate `openapi.json` specifications. See the [utoipa example](https://github.com/murar8/axum_typed_multipart/tree/main/examples/utoipa.rs)
 //! for integration details.
 //!
-//! Note: File uploads in `utoipa` require `Vec<u8>` which differs from this crate's `Bytes` or
-//! [tempfile::NamedTempFile](tempfile_3::NamedTempFile). The example shows how to handle this.
+//! Note: File uploads in `utoipa` are described using `Vec<u8>`, which this crate supports as a
+//! byte payload (together with `Box<[u8]>` and `Arc<[u8]>`) so the same struct can be used for
+//! both. Large uploads should still use [tempfile::NamedTempFile](tempfile_3::NamedTempFile).
 //!
 //! ### Validation
 //!
@@ -285,11 +654,34 @@
 #![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
 
 pub use anyhow;
-pub use async_trait::async_trait;
 pub use axum_typed_multipart_macros::{TryFromField, TryFromMultipart};
 
 mod base_multipart;
+mod dynamic_multipart;
+mod field_collector;
 mod field_data;
+mod form_outcome;
+mod form_schema;
+#[cfg(feature = "serde")]
+mod multipart_deserializer;
+mod presence;
+mod safe_file_name;
+#[cfg(feature = "serde")]
+mod serde_multipart;
+#[cfg(feature = "infer_0_19")]
+mod sniff;
+#[cfg(any(
+    feature = "ciborium_0_2",
+    feature = "rmp_serde_1",
+    feature = "serde_json",
+    feature = "serde_norway_0_9",
+    feature = "toml_1",
+))]
+mod structured;
+#[cfg(feature = "tokio_1")]
+mod try_from_async_read;
+#[cfg(feature = "tokio_1")]
+mod try_from_bytes_blocking;
 mod try_from_chunks;
 mod try_from_field;
 mod try_from_multipart;
@@ -298,8 +690,45 @@ mod typed_multipart_error;
 
 pub(crate) mod util;
 
+#[doc(hidden)]
+pub mod __private;
+
 pub use crate::base_multipart::BaseMultipart;
+pub use crate::dynamic_multipart::DynamicMultipart;
+pub use crate::field_collector::FieldCollector;
 pub use crate::field_data::{FieldData, FieldMetadata};
+pub use crate::form_outcome::{FormOutcome, InvalidForm};
+pub use crate::form_schema::{FieldKind, FieldSchema, FormSchema};
+#[cfg(feature = "serde")]
+pub use crate::multipart_deserializer::MultipartDeserializer;
+pub use crate::presence::Presence;
+pub use crate::safe_file_name::SafeFileName;
+#[cfg(feature = "serde")]
+pub use crate::serde_multipart::SerdeMultipart;
+#[cfg(feature = "infer_0_19")]
+pub use crate::sniff::SniffedContentType;
+#[cfg(feature = "ciborium_0_2")]
+pub use crate::structured::Cbor;
+#[cfg(feature = "serde_json")]
+pub use crate::structured::Json;
+#[cfg(feature = "rmp_serde_1")]
+pub use crate::structured::MessagePack;
+#[cfg(any(
+    feature = "ciborium_0_2",
+    feature = "rmp_serde_1",
+    feature = "serde_json",
+    feature = "serde_norway_0_9",
+    feature = "toml_1",
+))]
+pub use crate::structured::Structured;
+#[cfg(feature = "toml_1")]
+pub use crate::structured::Toml;
+#[cfg(feature = "serde_norway_0_9")]
+pub use crate::structured::Yaml;
+#[cfg(feature = "tokio_1")]
+pub use crate::try_from_async_read::TryFromAsyncRead;
+#[cfg(feature = "tokio_1")]
+pub use crate::try_from_bytes_blocking::TryFromBytesBlocking;
 pub use crate::try_from_chunks::TryFromChunks;
 pub use crate::try_from_field::{TryFromField, TryFromFieldWithState};
 pub use crate::try_from_multipart::{TryFromMultipart, TryFromMultipartWithState};
@@ -0,0 +1,607 @@
+use crate::{util, TypedMultipartError};
+use axum::body::Bytes;
+use axum::extract::Multipart;
+use serde::de::value::StrDeserializer;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use std::any::type_name;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Implement the deserialization methods of scalar values by delegating to the
+/// [ValueDeserializer] returned by the supplied method.
+macro_rules! forward_to_deserialize_scalar {
+    ( $into_value: ident ) => {
+        forward_to_deserialize_scalar! {
+            $into_value,
+            deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+            deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+            deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+            deserialize_string deserialize_bytes deserialize_byte_buf
+        }
+    };
+    ( $into_value: ident, $( $method: ident )* ) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.$into_value().$method(visitor)
+            }
+        )*
+    };
+}
+
+/// Implement the deserialization methods of numeric values by parsing the textual representation.
+macro_rules! deserialize_number {
+    ( $( $method: ident => $visit: ident ($type: ty) )* ) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse::<$type>()?)
+            }
+        )*
+    };
+}
+
+/// [Deserializer](serde::Deserializer) over the fields of a multipart request.
+///
+/// The request is represented as a map from field names to values: fields sent more than once
+/// are deserialized as sequences, while for single values the last occurrence is used. Values
+/// are parsed from their textual representation, so numbers and booleans are accepted the same
+/// way as with [TryFromMultipart](crate::TryFromMultipart).
+///
+/// Errors are reported as [TypedMultipartError] variants holding the name of the field that
+/// caused them, e.g. `tags[1]` for the second occurrence of the `tags` field.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum::extract::Multipart;
+/// use axum_typed_multipart::{MultipartDeserializer, TypedMultipartError};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Data {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// async fn handler(mut multipart: Multipart) -> Result<String, TypedMultipartError> {
+///     let deserializer = MultipartDeserializer::from_multipart(&mut multipart).await?;
+///     let data = Data::deserialize(deserializer)?;
+///     Ok(format!("{}: {}", data.name, data.tags.join(", ")))
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MultipartDeserializer {
+    fields: Vec<(String, Vec<Bytes>)>,
+}
+
+impl MultipartDeserializer {
+    /// Read all the fields of the request into memory.
+    pub async fn from_multipart(multipart: &mut Multipart) -> Result<Self, TypedMultipartError> {
+        let mut fields = Vec::new();
+
+        while let Some(field) = multipart.next_field().await? {
+            let name = match field.name() {
+                Some(name) if !name.is_empty() => name.to_owned(),
+                _ => return Err(TypedMultipartError::NamelessField),
+            };
+            fields.push((name, field.bytes().await?));
+        }
+
+        Ok(Self::from_fields(fields))
+    }
+
+    /// Create a deserializer from a list of field names and values.
+    pub fn from_fields(fields: impl IntoIterator<Item = (String, Bytes)>) -> Self {
+        let mut grouped = Vec::<(String, Vec<Bytes>)>::new();
+        // Position of each name in `grouped`, which keeps the order of the first occurrences.
+        let mut indices = HashMap::<String, usize>::new();
+
+        for (name, value) in fields {
+            match indices.get(&name) {
+                Some(&index) => grouped[index].1.push(value),
+                None => {
+                    indices.insert(name.clone(), grouped.len());
+                    grouped.push((name, vec![value]));
+                }
+            }
+        }
+
+        Self { fields: grouped }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for MultipartDeserializer {
+    type Error = TypedMultipartError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(FieldsAccess { fields: self.fields.into_iter(), current: None })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Errors raised by [Deserialize](serde::Deserialize) implementations.
+///
+/// Errors raised while deserializing a value are wrapped in
+/// [InvalidData](TypedMultipartError::InvalidData) and converted to
+/// [WrongFieldType](TypedMultipartError::WrongFieldType) once the name of the field is known.
+/// Errors that do not belong to a single field, such as the ones of a struct-level
+/// `#[serde(try_from)]`, are returned as they are.
+impl de::Error for TypedMultipartError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::InvalidData { source: anyhow::anyhow!("{msg}") }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::MissingField { field_name: field.to_owned() }
+    }
+
+    fn unknown_field(field: &str, _: &'static [&'static str]) -> Self {
+        Self::UnknownField { field_name: field.to_owned() }
+    }
+
+    fn duplicate_field(field: &'static str) -> Self {
+        Self::DuplicateField { field_name: field.to_owned() }
+    }
+}
+
+/// Attach the field name to an error raised while deserializing a value of type `T`.
+fn with_field_name<T>(err: TypedMultipartError, field_name: &str) -> TypedMultipartError {
+    match err {
+        TypedMultipartError::InvalidData { source } => TypedMultipartError::WrongFieldType {
+            field_name: field_name.to_owned(),
+            wanted_type: type_name::<T>().to_string(),
+            source,
+        },
+        err => err,
+    }
+}
+
+struct FieldsAccess {
+    fields: std::vec::IntoIter<(String, Vec<Bytes>)>,
+    current: Option<(String, Vec<Bytes>)>,
+}
+
+impl<'de> MapAccess<'de> for FieldsAccess {
+    type Error = TypedMultipartError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((name, values)) = self.fields.next() else {
+            return Ok(None);
+        };
+
+        let key = seed.deserialize(StrDeserializer::<Self::Error>::new(&name))?;
+        self.current = Some((name, values));
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (name, values) = self.current.take().expect("value requested before key");
+        seed.deserialize(FieldDeserializer { name: &name, values })
+            .map_err(|err| with_field_name::<V::Value>(err, &name))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+/// Deserializer over all the occurrences of a field.
+struct FieldDeserializer<'a> {
+    name: &'a str,
+    values: Vec<Bytes>,
+}
+
+impl<'a> FieldDeserializer<'a> {
+    fn into_value(mut self) -> ValueDeserializer<'a> {
+        // The map only contains fields with at least one occurrence.
+        let bytes = self.values.pop().unwrap_or_default();
+        ValueDeserializer { name: self.name, bytes }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'_> {
+    type Error = TypedMultipartError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.values.len() {
+            1 => self.into_value().deserialize_any(visitor),
+            _ => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let values = self.values.into_iter().enumerate();
+        visitor.visit_seq(ValuesAccess { name: self.name, values })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.into_value().deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_scalar! { into_value }
+
+    forward_to_deserialize_any! {
+        unit unit_struct map struct identifier ignored_any
+    }
+}
+
+struct ValuesAccess<'a> {
+    name: &'a str,
+    values: std::iter::Enumerate<std::vec::IntoIter<Bytes>>,
+}
+
+impl<'de> SeqAccess<'de> for ValuesAccess<'_> {
+    type Error = TypedMultipartError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, bytes)) = self.values.next() else {
+            return Ok(None);
+        };
+
+        let name = format!("{}[{index}]", self.name);
+        seed.deserialize(ValueDeserializer { name: &name, bytes })
+            .map(Some)
+            .map_err(|err| with_field_name::<T::Value>(err, &name))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Deserializer over a single occurrence of a field.
+struct ValueDeserializer<'a> {
+    name: &'a str,
+    bytes: Bytes,
+}
+
+impl ValueDeserializer<'_> {
+    fn wrong_type<T>(&self, source: impl Into<anyhow::Error>) -> TypedMultipartError {
+        TypedMultipartError::WrongFieldType {
+            field_name: self.name.to_owned(),
+            wanted_type: type_name::<T>().to_string(),
+            source: source.into(),
+        }
+    }
+
+    fn text<T>(&self) -> Result<&str, TypedMultipartError> {
+        std::str::from_utf8(&self.bytes).map_err(|err| self.wrong_type::<T>(err))
+    }
+
+    fn parse<T>(&self) -> Result<T, TypedMultipartError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.text::<T>()?.parse().map_err(|err| self.wrong_type::<T>(err))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = TypedMultipartError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match std::str::from_utf8(&self.bytes) {
+            Ok(text) => visitor.visit_str(text),
+            Err(_) => visitor.visit_bytes(&self.bytes),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match util::str_to_bool(self.text::<bool>()?) {
+            Some(value) => visitor.visit_bool(value),
+            None => Err(self.wrong_type::<bool>(anyhow::anyhow!("Invalid boolean value"))),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8(i8)
+        deserialize_i16 => visit_i16(i16)
+        deserialize_i32 => visit_i32(i32)
+        deserialize_i64 => visit_i64(i64)
+        deserialize_i128 => visit_i128(i128)
+        deserialize_u8 => visit_u8(u8)
+        deserialize_u16 => visit_u16(u16)
+        deserialize_u32 => visit_u32(u32)
+        deserialize_u64 => visit_u64(u64)
+        deserialize_u128 => visit_u128(u128)
+        deserialize_f32 => visit_f32(f32)
+        deserialize_f64 => visit_f64(f64)
+        deserialize_char => visit_char(char)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.text::<String>()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_bytes(&self.bytes)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(Vec::from(self.bytes))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let variant: StrDeserializer<'_, Self::Error> = self.text::<String>()?.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    forward_to_deserialize_any! {
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer};
+    use std::fmt;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Sort {
+        Name,
+        Date,
+    }
+
+    /// Binary value read through [Deserializer::deserialize_bytes].
+    #[derive(Debug, PartialEq)]
+    struct Blob(Vec<u8>);
+
+    impl<'de> Deserialize<'de> for Blob {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct BlobVisitor;
+
+            impl Visitor<'_> for BlobVisitor {
+                type Value = Blob;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("bytes")
+                }
+
+                fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                    Ok(Blob(value.to_vec()))
+                }
+            }
+
+            deserializer.deserialize_bytes(BlobVisitor)
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Data {
+        name: String,
+        age: u8,
+        active: bool,
+        score: Option<f32>,
+        nickname: Option<String>,
+        tags: Vec<String>,
+        ids: Vec<u32>,
+        sort: Sort,
+        avatar: Blob,
+    }
+
+    fn deserialize<T: DeserializeOwned>(
+        fields: &[(&str, &[u8])],
+    ) -> Result<T, TypedMultipartError> {
+        let fields =
+            fields.iter().map(|(name, value)| (name.to_string(), Bytes::copy_from_slice(value)));
+        T::deserialize(MultipartDeserializer::from_fields(fields))
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let data = deserialize::<Data>(&[
+            ("name", b"John"),
+            ("age", b"42"),
+            ("active", b"on"),
+            ("score", b"1.5"),
+            ("tags", b"a"),
+            ("ids", b"1"),
+            ("tags", b"b"),
+            ("ids", b"2"),
+            ("sort", b"date"),
+            ("avatar", &[0x80, 0x81]),
+        ])
+        .unwrap();
+
+        assert_eq!(data.name, "John");
+        assert_eq!(data.age, 42);
+        assert!(data.active);
+        assert_eq!(data.score, Some(1.5));
+        assert_eq!(data.nickname, None);
+        assert_eq!(data.tags, ["a", "b"]);
+        assert_eq!(data.ids, [1, 2]);
+        assert_eq!(data.sort, Sort::Date);
+        assert_eq!(data.avatar, Blob(vec![0x80, 0x81]));
+    }
+
+    #[test]
+    fn test_deserialize_last_occurrence() {
+        #[derive(Deserialize)]
+        struct Data {
+            name: String,
+            tags: Vec<String>,
+        }
+
+        let data = deserialize::<Data>(&[("name", b"John"), ("name", b"Jane"), ("tags", b"a")]);
+        let data = data.unwrap();
+        assert_eq!(data.name, "Jane");
+        assert_eq!(data.tags, ["a"]);
+    }
+
+    #[test]
+    fn test_deserialize_wrong_field_type() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Data {
+            age: u8,
+            ids: Vec<u32>,
+            sort: Option<Sort>,
+        }
+
+        let res = deserialize::<Data>(&[("age", b"old")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, wanted_type, .. })
+                if field_name == "age" && wanted_type == "u8"
+        ));
+
+        let res = deserialize::<Data>(&[("age", b"1"), ("ids", b"1"), ("ids", b"x")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, .. }) if field_name == "ids[1]"
+        ));
+
+        let res = deserialize::<Data>(&[("age", b"1"), ("ids", b"1"), ("sort", b"size")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, .. }) if field_name == "sort"
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_struct_errors() {
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Data {
+            name: String,
+        }
+
+        let res = deserialize::<Data>(&[]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::MissingField { field_name }) if field_name == "name"
+        ));
+
+        let res = deserialize::<Data>(&[("name", b"John"), ("age", b"42")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::UnknownField { field_name }) if field_name == "age"
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_invalid_data() {
+        #[derive(Deserialize)]
+        struct Bounds {
+            start: u8,
+            end: u8,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(try_from = "Bounds")]
+        #[allow(dead_code)]
+        struct Range(u8, u8);
+
+        impl TryFrom<Bounds> for Range {
+            type Error = &'static str;
+
+            fn try_from(Bounds { start, end }: Bounds) -> Result<Self, Self::Error> {
+                if start > end {
+                    return Err("start is after end");
+                }
+                Ok(Range(start, end))
+            }
+        }
+
+        let res = deserialize::<Range>(&[("start", b"2"), ("end", b"1")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::InvalidData { source })
+                if source.to_string() == "start is after end"
+        ));
+
+        let res = deserialize::<Range>(&[("start", b"x"), ("end", b"1")]);
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, .. }) if field_name == "start"
+        ));
+    }
+}
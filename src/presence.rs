@@ -0,0 +1,176 @@
+use crate::{FieldCollector, FieldMetadata, TryFromChunks, TypedMultipartError};
+use axum::body::Bytes;
+use futures_core::stream::Stream;
+use futures_util::stream::{self, StreamExt};
+use std::pin::pin;
+
+/// Tri-state field value, useful for partial updates (e.g. `PATCH` endpoints).
+///
+/// Unlike [Option], it distinguishes between a field that was not sent at all and a field that
+/// was sent without contents, which usually means that the value should be cleared.
+///
+/// When used in a struct deriving [TryFromMultipart](crate::TryFromMultipart) the field is never
+/// reported as missing and defaults to [Presence::Absent].
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::{Presence, TryFromMultipart};
+///
+/// #[derive(TryFromMultipart)]
+/// struct UpdateUserRequest {
+///     nickname: Presence<String>,
+///     age: Presence<u32>,
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Presence<T> {
+    /// The field was not present in the request.
+    #[default]
+    Absent,
+
+    /// The field was present in the request with empty contents.
+    Empty,
+
+    /// The field was present in the request and contained a value.
+    Value(T),
+}
+
+impl<T> Presence<T> {
+    /// Returns `true` if the field was not present in the request.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// Returns `true` if the field was present in the request with empty contents.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Returns `true` if the field was present in the request and contained a value.
+    pub fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    /// Converts from `&Presence<T>` to `Presence<&T>`.
+    pub fn as_ref(&self) -> Presence<&T> {
+        match self {
+            Self::Absent => Presence::Absent,
+            Self::Empty => Presence::Empty,
+            Self::Value(value) => Presence::Value(value),
+        }
+    }
+
+    /// Maps the contained value, leaving [Presence::Absent] and [Presence::Empty] untouched.
+    pub fn map<U, F>(self, f: F) -> Presence<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Self::Absent => Presence::Absent,
+            Self::Empty => Presence::Empty,
+            Self::Value(value) => Presence::Value(f(value)),
+        }
+    }
+
+    /// Returns the contained value, if any.
+    pub fn value(self) -> Option<T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> TryFromChunks for Presence<T>
+where
+    T: TryFromChunks,
+{
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        let mut chunks = pin!(chunks);
+        let first = loop {
+            match chunks.next().await {
+                Some(Ok(chunk)) if chunk.is_empty() => continue,
+                Some(chunk) => break chunk?,
+                None => return Ok(Self::Empty),
+            }
+        };
+
+        let chunks = stream::iter([Ok(first)]).chain(chunks);
+        T::try_from_chunks(chunks, metadata).await.map(Self::Value)
+    }
+}
+
+impl<T> FieldCollector for Presence<T> {
+    type Item = Self;
+    type Builder = Self;
+
+    const MULTIPLE: bool = false;
+
+    fn init() -> Self::Builder {
+        Self::Absent
+    }
+
+    fn push(builder: &mut Self::Builder, item: Self::Item) {
+        *builder = item;
+    }
+
+    fn finish(builder: Self::Builder) -> Option<Self> {
+        Some(builder)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+
+    fn create_chunks(
+        chunks: &[&'static str],
+    ) -> impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin {
+        stream::iter(
+            chunks.iter().map(|chunk| Ok(Bytes::from_static(chunk.as_bytes()))).collect::<Vec<_>>(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_presence_empty() {
+        let metadata = FieldMetadata::default();
+        let res = Presence::<u32>::try_from_chunks(create_chunks(&[]), metadata).await;
+        assert_eq!(res.unwrap(), Presence::Empty);
+
+        let metadata = FieldMetadata::default();
+        let res = Presence::<u32>::try_from_chunks(create_chunks(&["", ""]), metadata).await;
+        assert_eq!(res.unwrap(), Presence::Empty);
+    }
+
+    #[tokio::test]
+    async fn test_presence_value() {
+        let metadata = FieldMetadata::default();
+        let res = Presence::<u32>::try_from_chunks(create_chunks(&["", "4", "2"]), metadata).await;
+        assert_eq!(res.unwrap(), Presence::Value(42));
+    }
+
+    #[tokio::test]
+    async fn test_presence_invalid() {
+        let metadata = FieldMetadata::default();
+        let res = Presence::<u32>::try_from_chunks(create_chunks(&["invalid"]), metadata).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[test]
+    fn test_presence_accessors() {
+        let value = Presence::Value(42);
+        assert!(value.is_value());
+        assert_eq!(value.as_ref().map(|v| v + 1), Presence::Value(43));
+        assert_eq!(value.value(), Some(42));
+
+        assert!(Presence::<u32>::Absent.is_absent());
+        assert!(Presence::<u32>::Empty.is_empty());
+        assert_eq!(Presence::<u32>::Empty.value(), None);
+        assert_eq!(Presence::<u32>::default(), Presence::Absent);
+    }
+}
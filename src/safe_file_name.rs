@@ -0,0 +1,278 @@
+use axum::http::header::CONTENT_DISPOSITION;
+use axum::http::HeaderMap;
+use std::fmt;
+use std::ops::Deref;
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+/// Maximum length of a file name in bytes, as supported by most file systems.
+const MAX_LEN: usize = 255;
+
+/// Maximum length of an extension preserved when the name has to be truncated.
+const MAX_EXTENSION_LEN: usize = 16;
+
+/// Characters that are not allowed in file names on Windows.
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// Device names that cannot be used as file names on Windows, with or without an extension.
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// File name supplied by the client, sanitized so that it can be joined to a directory.
+///
+/// The name is created with [SafeFileName::new] or
+/// [FieldMetadata::safe_file_name](crate::FieldMetadata::safe_file_name), which:
+///
+/// - strips any directory, using both `/` and `\` as separators;
+/// - normalizes the name to the Unicode NFC form;
+/// - replaces the characters not allowed on Windows with `_`;
+/// - removes leading dots, so the file cannot be hidden, and trailing dots and spaces;
+/// - truncates the name to 255 bytes, preserving an alphanumeric extension.
+///
+/// Names containing control characters, empty names and the names reserved on Windows, such as
+/// `CON` or `nul.txt`, are rejected.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::SafeFileName;
+///
+/// let file_name = SafeFileName::new("../../etc/passwd").unwrap();
+/// assert_eq!(file_name.as_str(), "passwd");
+///
+/// assert!(SafeFileName::new("NUL.txt").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafeFileName(String);
+
+impl SafeFileName {
+    /// Sanitize the supplied file name, returning [None] if it cannot be made safe.
+    pub fn new(file_name: &str) -> Option<Self> {
+        let file_name = file_name.nfc().collect::<String>();
+        let file_name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+
+        if file_name.chars().any(char::is_control) {
+            return None;
+        }
+
+        let file_name = file_name.replace(RESERVED_CHARS, "_");
+        let file_name = file_name.trim_start_matches(['.', ' ']).trim_end_matches(['.', ' ']);
+        if file_name.is_empty() {
+            return None;
+        }
+
+        let stem = file_name.split('.').next().unwrap_or_default().trim_end();
+        if RESERVED_NAMES.iter().any(|name| name.eq_ignore_ascii_case(stem)) {
+            return None;
+        }
+
+        Some(Self(truncate(file_name)))
+    }
+
+    /// Returns the sanitized file name.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the extension of the file name, if it is alphanumeric and at most 16 characters
+    /// long.
+    pub fn extension(&self) -> Option<&str> {
+        let (_, extension) = self.0.rsplit_once('.')?;
+        is_safe_extension(extension).then_some(extension)
+    }
+
+    /// Decode the RFC 5987 `filename*` parameter of the `Content-Disposition` header.
+    ///
+    /// Only the `UTF-8` and `ISO-8859-1` character sets are supported.
+    pub(crate) fn extended_file_name(headers: &HeaderMap) -> Option<String> {
+        let header = headers.get(CONTENT_DISPOSITION)?.to_str().ok()?;
+        let value = parameters(header).find_map(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("filename*").then_some(value.trim())
+        })?;
+
+        let (charset, rest) = value.split_once('\'')?;
+        let (_, encoded) = rest.split_once('\'')?;
+        let bytes = percent_decode(encoded)?;
+
+        if charset.eq_ignore_ascii_case("UTF-8") {
+            String::from_utf8(bytes).ok()
+        } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+            Some(bytes.into_iter().map(char::from).collect())
+        } else {
+            None
+        }
+    }
+}
+
+impl Deref for SafeFileName {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SafeFileName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for SafeFileName {
+    fn as_ref(&self) -> &Path {
+        Path::new(&self.0)
+    }
+}
+
+impl fmt::Display for SafeFileName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<SafeFileName> for String {
+    fn from(file_name: SafeFileName) -> Self {
+        file_name.0
+    }
+}
+
+fn is_safe_extension(extension: &str) -> bool {
+    !extension.is_empty()
+        && extension.len() <= MAX_EXTENSION_LEN
+        && extension.bytes().all(|byte| byte.is_ascii_alphanumeric())
+}
+
+/// Truncate the file name to [MAX_LEN] bytes, keeping its extension if it is safe.
+fn truncate(file_name: &str) -> String {
+    if file_name.len() <= MAX_LEN {
+        return file_name.to_owned();
+    }
+
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if is_safe_extension(extension) => (stem, Some(extension)),
+        _ => (file_name, None),
+    };
+
+    let stem_len = MAX_LEN - extension.map_or(0, |extension| extension.len() + 1);
+    let end = (0..=stem_len).rev().find(|&end| stem.is_char_boundary(end)).unwrap_or_default();
+    let stem = stem[..end].trim_end_matches(['.', ' ']);
+
+    match extension {
+        Some(extension) => format!("{stem}.{extension}"),
+        None => stem.to_owned(),
+    }
+}
+
+/// Split the parameters of a header value on `;`, ignoring those inside quoted strings.
+fn parameters(header: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut quoted = false;
+    let mut escaped = false;
+    header
+        .split(move |c| {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if quoted => escaped = true,
+                '"' => quoted = !quoted,
+                ';' if !quoted => return true,
+                _ => {}
+            }
+            false
+        })
+        .filter_map(|parameter| parameter.split_once('='))
+}
+
+fn percent_decode(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn safe(file_name: &str) -> Option<String> {
+        SafeFileName::new(file_name).map(String::from)
+    }
+
+    #[test]
+    fn test_safe_file_name() {
+        assert_eq!(safe("photo.jpg").as_deref(), Some("photo.jpg"));
+        assert_eq!(safe("../../etc/passwd").as_deref(), Some("passwd"));
+        assert_eq!(safe("C:\\Users\\me\\report.pdf").as_deref(), Some("report.pdf"));
+        assert_eq!(safe(".htaccess").as_deref(), Some("htaccess"));
+        assert_eq!(safe("notes.txt. . ").as_deref(), Some("notes.txt"));
+        assert_eq!(safe("what?.txt").as_deref(), Some("what_.txt"));
+        assert_eq!(safe("cafe\u{301}.txt").as_deref(), Some("caf\u{e9}.txt"));
+    }
+
+    #[test]
+    fn test_safe_file_name_rejected() {
+        for file_name in
+            ["", ".", "..", "dir/", "a\0b.txt", "line\nbreak", "CON", "nul.txt", "Com1"]
+        {
+            assert_eq!(safe(file_name), None, "{file_name:?}");
+        }
+        assert!(safe("CONSOLE.txt").is_some());
+    }
+
+    #[test]
+    fn test_safe_file_name_truncated() {
+        let file_name = safe(&format!("{}.png", "a".repeat(300))).unwrap();
+        assert_eq!(file_name.len(), MAX_LEN);
+        assert!(file_name.ends_with("a.png"));
+
+        let file_name = safe(&format!("{}.png", "é".repeat(200))).unwrap();
+        assert!(file_name.len() <= MAX_LEN);
+        assert!(file_name.ends_with("é.png"));
+
+        let file_name = safe(&"a.b".repeat(100)).unwrap();
+        assert_eq!(file_name.len(), MAX_LEN);
+    }
+
+    #[test]
+    fn test_extension() {
+        assert_eq!(SafeFileName::new("archive.tar.gz").unwrap().extension(), Some("gz"));
+        assert_eq!(SafeFileName::new("README").unwrap().extension(), None);
+        assert_eq!(SafeFileName::new("image.p_ng").unwrap().extension(), None);
+    }
+
+    #[test]
+    fn test_extended_file_name() {
+        let headers = |value: &'static str| {
+            HeaderMap::from_iter([(CONTENT_DISPOSITION, HeaderValue::from_static(value))])
+        };
+
+        let value = r#"form-data; name="file"; filename="EUR rates.txt"; filename*=UTF-8''%e2%82%ac%20rates.txt"#;
+        let res = SafeFileName::extended_file_name(&headers(value));
+        assert_eq!(res.as_deref(), Some("€ rates.txt"));
+
+        let value = r#"form-data; name="a;b"; filename*=iso-8859-1'en'%A3%20rates.txt"#;
+        let res = SafeFileName::extended_file_name(&headers(value));
+        assert_eq!(res.as_deref(), Some("£ rates.txt"));
+
+        for value in [
+            r#"form-data; name="file"; filename="rates.txt""#,
+            "form-data; filename*=UTF-16''%00",
+            "form-data; filename*=UTF-8''%ff",
+            "form-data; filename*=UTF-8''%e",
+            "form-data; filename*=rates.txt",
+        ] {
+            assert_eq!(SafeFileName::extended_file_name(&headers(value)), None, "{value}");
+        }
+        assert_eq!(SafeFileName::extended_file_name(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{MultipartDeserializer, TypedMultipartError};
+use axum::extract::{FromRequest, Multipart, Request};
+use serde::de::DeserializeOwned;
+use std::ops::{Deref, DerefMut};
+
+/// Extractor for multipart form data deserialized with [serde].
+///
+/// Implements [FromRequest] when `T` implements [DeserializeOwned], allowing the models already
+/// used for other formats to be reused without deriving
+/// [TryFromMultipart](crate::TryFromMultipart). See [MultipartDeserializer] for how the request
+/// is mapped to `T`.
+///
+/// The whole request is read into memory before `T` is deserialized, so this extractor is not
+/// suitable for large uploads.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum::http::StatusCode;
+/// use axum_typed_multipart::SerdeMultipart;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Data {
+///     name: String,
+///     age: Option<u8>,
+///     tags: Vec<String>,
+/// }
+///
+/// async fn handle_data(data: SerdeMultipart<Data>) -> StatusCode {
+///     println!("name: {}", data.name);
+///     println!("age: {:?}", data.age);
+///     println!("tags: {}", data.tags.join(", "));
+///     StatusCode::OK
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SerdeMultipart<T>(pub T);
+
+impl<T> Deref for SerdeMultipart<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SerdeMultipart<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequest<S> for SerdeMultipart<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = TypedMultipartError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = &mut Multipart::from_request(req, state).await?;
+        let deserializer = MultipartDeserializer::from_multipart(multipart).await?;
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use axum_test_helper::TestClient;
+    use reqwest::multipart::Form;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Data {
+        name: String,
+        age: u8,
+    }
+
+    #[tokio::test]
+    async fn test_serde_multipart() {
+        async fn handler(SerdeMultipart(data): SerdeMultipart<Data>) {
+            assert_eq!(data.name, "John");
+            assert_eq!(data.age, 42);
+        }
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(Form::new().text("name", "John").text("age", "42"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_serde_multipart_rejection() {
+        async fn handler(_: SerdeMultipart<Data>) {
+            panic!("should not be called");
+        }
+
+        let res = TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(Form::new().text("name", "John").text("age", "old"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await.unwrap(),
+            "field 'age' must be of type 'u8': invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{util, FieldData, FieldMetadata, TryFromChunks, TryFromField, TypedMultipartError};
+use axum::extract::multipart::Field;
+use bytes::{Bytes, BytesMut};
+use std::future::Future;
+use std::mem;
+use std::sync::{Arc, OnceLock};
+
+/// Media type reported for contents whose type cannot be detected.
+const UNKNOWN_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Number of bytes collected before detecting the content type.
+///
+/// `infer` recognizes most formats from a signature within the first 262 bytes, the extra room
+/// allows container formats, such as Office documents, to be told apart from plain archives.
+const SNIFF_LEN: usize = 8 * 1024;
+
+/// Check applied to the detected content type, as configured by the `sniff` parameter of
+/// `#[form_data(...)]`.
+#[derive(Debug, Clone, Copy)]
+pub enum SniffRule {
+    /// The detected content type must match the `Content-Type` declared by the client.
+    ///
+    /// Contents whose type cannot be detected, such as plain text, and empty contents, such as
+    /// file inputs left blank, are accepted.
+    AgreeWithDeclared,
+
+    /// The detected content type must match one of the supplied patterns.
+    ///
+    /// Empty contents are rejected, since their type cannot be detected.
+    Allow(&'static [&'static str]),
+}
+
+/// Content type detected from the magic bytes at the start of the field contents.
+///
+/// Unlike [content_type](crate::FieldMetadata::content_type), the value is not supplied by the
+/// client. The detection happens while the contents are being read, so the value is available
+/// once the field has been parsed, e.g. in [FieldData], and is shared between the copies of the
+/// metadata.
+#[derive(Debug, Clone)]
+pub struct SniffedContentType {
+    detected: Arc<OnceLock<Option<&'static str>>>,
+    rule: SniffRule,
+}
+
+impl SniffedContentType {
+    pub(crate) fn new(rule: SniffRule) -> Self {
+        Self { detected: Arc::default(), rule }
+    }
+
+    /// Returns the detected media type, or [None] if it could not be detected or the contents
+    /// have not been read yet.
+    pub fn get(&self) -> Option<&'static str> {
+        self.detected.get().copied().flatten()
+    }
+
+    /// Detect the content type from the first bytes of the field and apply the [SniffRule].
+    fn sniff(&self, field: &Field<'_>, header: &[u8]) -> Result<(), TypedMultipartError> {
+        let detected = infer_0_19::get(header).map(|kind| kind.mime_type());
+        let _ = self.detected.set(detected);
+
+        let allowed = match self.rule {
+            SniffRule::AgreeWithDeclared => detected.is_none_or(|detected| {
+                let declared = field.content_type().unwrap_or("text/plain");
+                util::content_type_matches(declared, detected)
+            }),
+            SniffRule::Allow(patterns) => detected.is_some_and(|detected| {
+                patterns.iter().any(|pattern| util::content_type_matches(detected, pattern))
+            }),
+        };
+
+        if allowed {
+            return Ok(());
+        }
+
+        Err(TypedMultipartError::InvalidContentType {
+            field_name: field.name().unwrap_or_default().to_owned(),
+            content_type: detected.unwrap_or(UNKNOWN_CONTENT_TYPE).to_owned(),
+        })
+    }
+}
+
+/// Holds back the first chunks of a field until enough bytes are received to detect its
+/// content type, since clients may send the contents in arbitrarily small pieces.
+pub(crate) struct Sniffer {
+    sniffed_content_type: SniffedContentType,
+    header: BytesMut,
+    done: bool,
+}
+
+impl Sniffer {
+    pub(crate) fn new(sniffed_content_type: SniffedContentType) -> Self {
+        Self { sniffed_content_type, header: BytesMut::new(), done: false }
+    }
+
+    /// Add a chunk of the field, returning the bytes that can be passed on to the parser, if
+    /// any.
+    ///
+    /// The collected bytes are returned at once after the content type has been checked, the
+    /// following chunks are returned as they are.
+    pub(crate) fn push(
+        &mut self,
+        field: &Field<'_>,
+        chunk: Bytes,
+    ) -> Option<Result<Bytes, TypedMultipartError>> {
+        if self.done {
+            return Some(Ok(chunk));
+        }
+
+        if self.header.is_empty() && chunk.len() >= SNIFF_LEN {
+            self.done = true;
+            return Some(self.sniffed_content_type.sniff(field, &chunk).map(|()| chunk));
+        }
+
+        self.header.extend_from_slice(&chunk);
+        (self.header.len() >= SNIFF_LEN).then(|| self.flush(field))
+    }
+
+    /// Check the bytes collected for a field shorter than [SNIFF_LEN], once it has ended.
+    ///
+    /// Empty fields are only checked with [SniffRule::Allow], see [SniffRule].
+    pub(crate) fn finish(
+        &mut self,
+        field: &Field<'_>,
+    ) -> Option<Result<Bytes, TypedMultipartError>> {
+        if self.done {
+            return None;
+        }
+
+        let allow_empty = matches!(self.sniffed_content_type.rule, SniffRule::AgreeWithDeclared);
+        if self.header.is_empty() && allow_empty {
+            return None;
+        }
+
+        Some(self.flush(field))
+    }
+
+    fn flush(&mut self, field: &Field<'_>) -> Result<Bytes, TypedMultipartError> {
+        self.done = true;
+        let header = mem::take(&mut self.header).freeze();
+        self.sniffed_content_type.sniff(field, &header).map(|()| header)
+    }
+}
+
+/// Types supporting the `sniff` parameter of `#[form_data(...)]`.
+///
+/// The content type can only be detected while the contents are read through [TryFromChunks],
+/// so types implementing [TryFromField](crate::TryFromField) directly are not supported.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not support `sniff`",
+    note = "`sniff` requires a type implementing `TryFromChunks`, optionally wrapped in `FieldData`"
+)]
+pub trait Sniff: Sized {
+    /// Parse the field, detecting its content type according to the `sniffed_content_type` of
+    /// the supplied metadata.
+    fn try_from_sniffed_field(
+        field: Field<'_>,
+        metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+
+    /// Handle an error raised for the field before it is parsed, see
+    /// [TryFromChunks::from_field_error].
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        Err(err)
+    }
+}
+
+impl<T> Sniff for T
+where
+    T: TryFromChunks,
+{
+    fn try_from_sniffed_field(
+        field: Field<'_>,
+        metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        <T as TryFromField>::try_from_field_with_metadata(field, metadata, limit_bytes)
+    }
+
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        <T as TryFromChunks>::from_field_error(err)
+    }
+}
+
+impl<T> Sniff for FieldData<T>
+where
+    T: TryFromChunks + Send,
+{
+    async fn try_from_sniffed_field(
+        field: Field<'_>,
+        metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+    ) -> Result<Self, TypedMultipartError> {
+        let contents =
+            <T as TryFromField>::try_from_field_with_metadata(field, metadata.clone(), limit_bytes)
+                .await?;
+        Ok(Self { metadata, contents })
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::{FromRequest, Multipart, Request};
+    use axum::http::header::CONTENT_TYPE;
+    use futures_util::stream;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const PDF: &[u8] = b"%PDF-1.7\n";
+
+    /// Parse a field whose contents are received in chunks of `chunk_size` bytes, yielding to the
+    /// runtime between chunks so that they are not merged.
+    async fn sniffed(
+        contents: Vec<u8>,
+        declared: &str,
+        chunk_size: usize,
+        rule: SniffRule,
+    ) -> Result<FieldData<Bytes>, TypedMultipartError> {
+        let mut body = format!(
+            "--X\r\nContent-Disposition: form-data; name=\"file\"\r\nContent-Type: {declared}\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(&contents);
+        body.extend_from_slice(b"\r\n--X--\r\n");
+
+        let chunks = body.chunks(chunk_size).map(Bytes::copy_from_slice).collect::<Vec<_>>();
+        let body = stream::unfold(chunks.into_iter(), |mut chunks| async move {
+            tokio::task::yield_now().await;
+            Some((Ok::<_, std::io::Error>(chunks.next()?), chunks))
+        });
+
+        let req = Request::builder()
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X")
+            .body(Body::from_stream(body))
+            .unwrap();
+        let mut multipart = Multipart::from_request(req, &()).await.unwrap();
+        let field = multipart.next_field().await.unwrap().unwrap();
+
+        crate::__private::sniffed(field, None, &[], rule).await
+    }
+
+    #[tokio::test]
+    async fn test_sniff_small_chunks() {
+        let rule = SniffRule::Allow(&["image/png"]);
+        let data = sniffed(PNG.to_vec(), "image/png", 1, rule).await.unwrap();
+        assert_eq!(data.contents, PNG);
+        assert_eq!(data.metadata.sniffed_content_type.unwrap().get(), Some("image/png"));
+
+        let res = sniffed(PDF.to_vec(), "image/png", 1, SniffRule::AgreeWithDeclared).await;
+        assert!(matches!(res, Err(TypedMultipartError::InvalidContentType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_sniff_large_contents() {
+        let contents = [PNG, &[0; 2 * SNIFF_LEN]].concat();
+        let rule = SniffRule::Allow(&["image/png"]);
+
+        for chunk_size in [1, 1000, 4 * SNIFF_LEN] {
+            let data = sniffed(contents.clone(), "image/png", chunk_size, rule).await.unwrap();
+            assert_eq!(data.contents, contents, "{chunk_size}");
+        }
+
+        let contents = [PDF, &[0; 2 * SNIFF_LEN]].concat();
+        let res = sniffed(contents, "image/png", 1000, rule).await;
+        assert!(matches!(res, Err(TypedMultipartError::InvalidContentType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_sniff_empty_field() {
+        let data = sniffed(Vec::new(), "image/png", 1, SniffRule::AgreeWithDeclared).await;
+        assert!(data.unwrap().contents.is_empty());
+
+        let res = sniffed(Vec::new(), "image/png", 1, SniffRule::Allow(&["image/png"])).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::InvalidContentType { content_type, .. })
+                if content_type == UNKNOWN_CONTENT_TYPE
+        ));
+    }
+}
@@ -0,0 +1,490 @@
+use crate::{util, FieldMetadata, TryFromChunks, TypedMultipartError};
+use axum::body::Bytes;
+use futures_core::stream::Stream;
+use serde::de::DeserializeOwned;
+use std::ops::{Deref, DerefMut};
+
+/// Serialization format of a structured field type.
+pub(crate) trait Format {
+    /// Name of the format, reported as the wanted type of invalid fields.
+    const NAME: &'static str;
+
+    /// Returns `true` if the supplied media type, without parameters, denotes the format.
+    fn accepts(essence: &str) -> bool;
+
+    /// Deserialize a value from the contents of the field.
+    fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> anyhow::Result<T>;
+}
+
+/// Parse a field using the format `F`, checking the `Content-Type` of the part when present.
+pub(crate) async fn parse<F, T>(
+    chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+    metadata: FieldMetadata,
+) -> Result<T, TypedMultipartError>
+where
+    F: Format,
+    T: DeserializeOwned,
+{
+    if let Some(content_type) = metadata.content_type.as_deref() {
+        if !F::accepts(&util::mime_essence(content_type)) {
+            return Err(invalid_content_type(metadata.name, content_type));
+        }
+    }
+
+    let field_name = metadata.name.clone().unwrap_or_default();
+    let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
+    F::from_slice(&bytes).map_err(|source| TypedMultipartError::WrongFieldType {
+        field_name,
+        wanted_type: F::NAME.to_owned(),
+        source,
+    })
+}
+
+fn invalid_content_type(field_name: Option<String>, content_type: &str) -> TypedMultipartError {
+    TypedMultipartError::InvalidContentType {
+        field_name: field_name.unwrap_or_default(),
+        content_type: content_type.to_owned(),
+    }
+}
+
+/// Generate a wrapper type deserializing the field with the supplied format.
+macro_rules! structured_format {
+    (
+        $(#[$attr: meta])*
+        $feature: literal,
+        $name: ident,
+        $format: literal,
+        |$essence: ident| $accepts: expr,
+        |$bytes: ident| $from_slice: expr $(,)?
+    ) => {
+        $(#[$attr])*
+        #[cfg(feature = $feature)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name<T>(pub T);
+
+        #[cfg(feature = $feature)]
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T> DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T> Format for $name<T> {
+            const NAME: &'static str = $format;
+
+            fn accepts($essence: &str) -> bool {
+                $accepts
+            }
+
+            fn from_slice<U: DeserializeOwned>($bytes: &[u8]) -> anyhow::Result<U> {
+                Ok($from_slice?)
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T> TryFromChunks for $name<T>
+        where
+            T: DeserializeOwned + Send,
+        {
+            async fn try_from_chunks(
+                chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+                metadata: FieldMetadata,
+            ) -> Result<Self, TypedMultipartError> {
+                parse::<Self, T>(chunks, metadata).await.map($name)
+            }
+        }
+    };
+}
+
+structured_format! {
+    /// Field containing a JSON document, deserialized with [serde_json].
+    ///
+    /// Useful when clients send structured data, such as the metadata of a file, in a part next
+    /// to the file itself. If the part declares a `Content-Type` it must be `application/json` or
+    /// a `+json` media type, e.g. `application/ld+json`.
+    ///
+    /// Invalid documents are reported as a
+    /// [WrongFieldType](TypedMultipartError::WrongFieldType) error including the line and column
+    /// of the syntax error.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use axum::body::Bytes;
+    /// use axum_typed_multipart::{Json, TryFromMultipart};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata {
+    ///     title: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// #[derive(TryFromMultipart)]
+    /// struct UploadRequest {
+    ///     metadata: Json<Metadata>,
+    ///     file: Bytes,
+    /// }
+    /// ```
+    "serde_json",
+    Json,
+    "JSON",
+    |essence| essence == "application/json" || essence.ends_with("+json"),
+    |bytes| serde_json::from_slice(bytes),
+}
+
+structured_format! {
+    /// Field containing a TOML document, deserialized with [toml](toml_1).
+    ///
+    /// If the part declares a `Content-Type` it must be `application/toml` or a `+toml` media
+    /// type.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use axum_typed_multipart::{Toml, TryFromMultipart};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     replicas: u32,
+    /// }
+    ///
+    /// #[derive(TryFromMultipart)]
+    /// struct DeployRequest {
+    ///     config: Toml<Config>,
+    /// }
+    /// ```
+    "toml_1",
+    Toml,
+    "TOML",
+    |essence| essence == "application/toml" || essence.ends_with("+toml"),
+    |bytes| toml_1::from_slice(bytes),
+}
+
+structured_format! {
+    /// Field containing a YAML document, deserialized with [serde_norway](serde_norway_0_9).
+    ///
+    /// If the part declares a `Content-Type` it must be `application/yaml`, `application/x-yaml`,
+    /// `text/yaml`, `text/x-yaml` or a `+yaml` media type.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use axum_typed_multipart::{TryFromMultipart, Yaml};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     replicas: u32,
+    /// }
+    ///
+    /// #[derive(TryFromMultipart)]
+    /// struct DeployRequest {
+    ///     config: Yaml<Config>,
+    /// }
+    /// ```
+    "serde_norway_0_9",
+    Yaml,
+    "YAML",
+    |essence| {
+        matches!(essence, "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml")
+            || essence.ends_with("+yaml")
+    },
+    |bytes| serde_norway_0_9::from_slice(bytes),
+}
+
+structured_format! {
+    /// Field containing a MessagePack value, deserialized with [rmp_serde](rmp_serde_1).
+    ///
+    /// If the part declares a `Content-Type` it must be `application/msgpack`,
+    /// `application/x-msgpack` or `application/vnd.msgpack`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use axum_typed_multipart::{MessagePack, TryFromMultipart};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Sample {
+    ///     timestamp: u64,
+    ///     value: f64,
+    /// }
+    ///
+    /// #[derive(TryFromMultipart)]
+    /// struct TelemetryRequest {
+    ///     samples: MessagePack<Vec<Sample>>,
+    /// }
+    /// ```
+    "rmp_serde_1",
+    MessagePack,
+    "MessagePack",
+    |essence| {
+        matches!(essence, "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack")
+    },
+    |bytes| rmp_serde_1::from_slice(bytes),
+}
+
+structured_format! {
+    /// Field containing a CBOR value, deserialized with [ciborium](ciborium_0_2).
+    ///
+    /// If the part declares a `Content-Type` it must be `application/cbor` or a `+cbor` media
+    /// type.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use axum_typed_multipart::{Cbor, TryFromMultipart};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Sample {
+    ///     timestamp: u64,
+    ///     value: f64,
+    /// }
+    ///
+    /// #[derive(TryFromMultipart)]
+    /// struct TelemetryRequest {
+    ///     samples: Cbor<Vec<Sample>>,
+    /// }
+    /// ```
+    "ciborium_0_2",
+    Cbor,
+    "CBOR",
+    |essence| essence == "application/cbor" || essence.ends_with("+cbor"),
+    |bytes| ciborium_0_2::from_reader(bytes),
+}
+
+/// Field containing structured data in any of the enabled formats, selected through the
+/// `Content-Type` of the part.
+///
+/// The supported formats are the ones of the [Json], [Toml], [Yaml], [MessagePack] and [Cbor]
+/// field types whose feature is enabled. Parts without a `Content-Type`, or with one that does
+/// not match any of the formats, are rejected with an
+/// [InvalidContentType](TypedMultipartError::InvalidContentType) error.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::{Structured, TryFromMultipart};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: String,
+///     replicas: u32,
+/// }
+///
+/// #[derive(TryFromMultipart)]
+/// struct DeployRequest {
+///     config: Structured<Config>, // accepts JSON, TOML, YAML, ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Structured<T>(pub T);
+
+impl<T> Deref for Structured<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Structured<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> TryFromChunks for Structured<T>
+where
+    T: DeserializeOwned + Send,
+{
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        let essence = metadata.content_type.as_deref().map(util::mime_essence).unwrap_or_default();
+
+        #[cfg(feature = "serde_json")]
+        if Json::<T>::accepts(&essence) {
+            return parse::<Json<T>, T>(chunks, metadata).await.map(Structured);
+        }
+        #[cfg(feature = "toml_1")]
+        if Toml::<T>::accepts(&essence) {
+            return parse::<Toml<T>, T>(chunks, metadata).await.map(Structured);
+        }
+        #[cfg(feature = "serde_norway_0_9")]
+        if Yaml::<T>::accepts(&essence) {
+            return parse::<Yaml<T>, T>(chunks, metadata).await.map(Structured);
+        }
+        #[cfg(feature = "rmp_serde_1")]
+        if MessagePack::<T>::accepts(&essence) {
+            return parse::<MessagePack<T>, T>(chunks, metadata).await.map(Structured);
+        }
+        #[cfg(feature = "ciborium_0_2")]
+        if Cbor::<T>::accepts(&essence) {
+            return parse::<Cbor<T>, T>(chunks, metadata).await.map(Structured);
+        }
+
+        // Parts without a `Content-Type` are treated as `text/plain`, like for the `content_type`
+        // parameter of `#[form_data(...)]`.
+        let content_type = metadata.content_type.as_deref().unwrap_or("text/plain");
+        Err(invalid_content_type(metadata.name, content_type))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Data {
+        name: String,
+    }
+
+    fn data() -> Data {
+        Data { name: "John".into() }
+    }
+
+    async fn parse<T: TryFromChunks>(
+        input: impl Into<Bytes>,
+        content_type: Option<&str>,
+    ) -> Result<T, TypedMultipartError> {
+        let chunks = stream::iter([Ok(input.into())]);
+        let metadata = FieldMetadata {
+            name: Some("data".into()),
+            content_type: content_type.map(String::from),
+            ..Default::default()
+        };
+        T::try_from_chunks(chunks, metadata).await
+    }
+
+    fn cbor(data: &Data) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium_0_2::into_writer(data, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_json() {
+        let res = parse::<Json<Data>>(r#"{"name":"John"}"#, None).await;
+        assert_eq!(res.unwrap(), Json(data()));
+
+        let content_type = Some("application/json; charset=utf-8");
+        let res = parse::<Json<Data>>(r#"{"name":"John"}"#, content_type).await;
+        assert_eq!(res.unwrap(), Json(data()));
+
+        let res = parse::<Json<Data>>(r#"{"name":"John"}"#, Some("application/ld+json")).await;
+        assert_eq!(res.unwrap(), Json(data()));
+    }
+
+    #[tokio::test]
+    async fn test_json_invalid() {
+        let res = parse::<Json<Data>>("{\n\"name\": 42}", None).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, wanted_type, source })
+                if field_name == "data"
+                    && wanted_type == "JSON"
+                    && source.to_string().ends_with("at line 2 column 10")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_content_type() {
+        let res = parse::<Json<Data>>(r#"{"name":"John"}"#, Some("text/plain")).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::InvalidContentType { field_name, content_type })
+                if field_name == "data" && content_type == "text/plain"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_toml() {
+        let res = parse::<Toml<Data>>(r#"name = "John""#, Some("application/toml")).await;
+        assert_eq!(res.unwrap(), Toml(data()));
+
+        let res = parse::<Toml<Data>>("name = ", None).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_yaml() {
+        let res = parse::<Yaml<Data>>("name: John", Some("application/yaml")).await;
+        assert_eq!(res.unwrap(), Yaml(data()));
+
+        let res = parse::<Yaml<Data>>("name: [", None).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_message_pack() {
+        let bytes = rmp_serde_1::to_vec(&data()).unwrap();
+        let res = parse::<MessagePack<Data>>(bytes, Some("application/msgpack")).await;
+        assert_eq!(res.unwrap(), MessagePack(data()));
+
+        let res = parse::<MessagePack<Data>>(vec![0xc1], None).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_cbor() {
+        let res = parse::<Cbor<Data>>(cbor(&data()), Some("application/cbor")).await;
+        assert_eq!(res.unwrap(), Cbor(data()));
+
+        let res = parse::<Cbor<Data>>(vec![0xff], None).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_structured() {
+        let inputs: [(Bytes, &str); 5] = [
+            (Bytes::from(r#"{"name":"John"}"#), "application/json"),
+            (Bytes::from(r#"name = "John""#), "application/toml"),
+            (Bytes::from("name: John"), "text/yaml"),
+            (Bytes::from(rmp_serde_1::to_vec(&data()).unwrap()), "application/vnd.msgpack"),
+            (Bytes::from(cbor(&data())), "application/cbor"),
+        ];
+
+        for (input, content_type) in inputs {
+            let res = parse::<Structured<Data>>(input, Some(content_type)).await;
+            assert_eq!(res.unwrap(), Structured(data()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_structured_unsupported() {
+        let res = parse::<Structured<Data>>("name=John", Some("text/csv")).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::InvalidContentType { content_type, .. })
+                if content_type == "text/csv"
+        ));
+
+        let res = parse::<Structured<Data>>(r#"{"name":"John"}"#, None).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::InvalidContentType { content_type, .. })
+                if content_type == "text/plain"
+        ));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{FieldMetadata, TryFromChunks, TypedMultipartError};
+use axum::body::Bytes;
+use futures_core::stream::Stream;
+use futures_util::stream::StreamExt;
+use std::any::type_name;
+use std::future::Future;
+use std::io;
+use std::pin::pin;
+use tokio::io::AsyncBufRead;
+use tokio_util::io::StreamReader;
+
+/// Types that can be created from an [AsyncBufRead] over the contents of a field.
+///
+/// Useful to reuse parsers that consume readers instead of streams, such as decoders or
+/// codecs. Every type implementing this trait automatically implements
+/// [TryFromChunks], and therefore [TryFromField](crate::TryFromField), so the field size limit
+/// still applies to the data read.
+///
+/// Errors returned by the reader can be converted with `?`: those raised while receiving the
+/// field, such as [FieldTooLarge](TypedMultipartError::FieldTooLarge), are converted back to the
+/// original error. Invalid contents should be reported as
+/// [WrongFieldType](TypedMultipartError::WrongFieldType), which is done automatically for
+/// [InvalidData](io::ErrorKind::InvalidData) and [UnexpectedEof](io::ErrorKind::UnexpectedEof)
+/// errors, e.g. raised when reading text that is not valid UTF-8.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum_typed_multipart::{FieldMetadata, TryFromAsyncRead, TypedMultipartError};
+/// use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+///
+/// /// Lines of a text file.
+/// struct Lines(Vec<String>);
+///
+/// impl TryFromAsyncRead for Lines {
+///     async fn try_from_async_read(
+///         reader: impl AsyncBufRead + Send + Unpin,
+///         _: FieldMetadata,
+///     ) -> Result<Self, TypedMultipartError> {
+///         let mut lines = reader.lines();
+///         let mut data = Vec::new();
+///         while let Some(line) = lines.next_line().await? {
+///             data.push(line);
+///         }
+///         Ok(Lines(data))
+///     }
+/// }
+/// ```
+pub trait TryFromAsyncRead: Sized {
+    /// Consume the supplied reader to create the type.
+    ///
+    /// The `metadata` parameter contains information about the field.
+    fn try_from_async_read(
+        reader: impl AsyncBufRead + Send + Unpin,
+        metadata: FieldMetadata,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+}
+
+impl<T> TryFromChunks for T
+where
+    T: TryFromAsyncRead,
+{
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        let field_name = metadata.name.clone().unwrap_or_default();
+        let chunks = pin!(chunks);
+        let reader = StreamReader::new(chunks.map(|chunk| chunk.map_err(io::Error::other)));
+        T::try_from_async_read(reader, metadata).await.map_err(|err| match err {
+            TypedMultipartError::InvalidData { source } => TypedMultipartError::WrongFieldType {
+                field_name,
+                wanted_type: type_name::<T>().to_string(),
+                source,
+            },
+            err => err,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use axum::extract::Multipart;
+    use axum::routing::post;
+    use axum::Router;
+    use axum_test_helper::TestClient;
+    use futures_util::stream;
+    use reqwest::multipart::Form;
+    use tokio::io::AsyncReadExt;
+
+    #[derive(Debug)]
+    struct Data(String);
+
+    impl TryFromAsyncRead for Data {
+        async fn try_from_async_read(
+            mut reader: impl AsyncBufRead + Send + Unpin,
+            _: FieldMetadata,
+        ) -> Result<Self, TypedMultipartError> {
+            let mut data = String::new();
+            reader.read_to_string(&mut data).await?;
+            Ok(Self(data))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_try_from_async_read() {
+        let chunks = stream::iter(["Hello, ", "world!"].map(|chunk| Ok(Bytes::from(chunk))));
+        let res = Data::try_from_chunks(chunks, FieldMetadata::default()).await;
+        assert_eq!(res.unwrap().0, "Hello, world!");
+    }
+
+    #[tokio::test]
+    async fn test_try_from_async_read_invalid() {
+        let chunks = stream::iter([Ok(Bytes::from_static(&[0x80]))]);
+        let metadata = FieldMetadata { name: Some("data".into()), ..Default::default() };
+        let res = Data::try_from_chunks(chunks, metadata).await;
+        assert!(matches!(
+            res,
+            Err(TypedMultipartError::WrongFieldType { field_name, .. }) if field_name == "data"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_try_from_async_read_too_large() {
+        let handler = |mut multipart: Multipart| async move {
+            let field = multipart.next_field().await.unwrap().unwrap();
+            let res = <Data as crate::TryFromField>::try_from_field(field, Some(512)).await;
+            assert!(matches!(res, Err(TypedMultipartError::FieldTooLarge { .. })));
+        };
+
+        TestClient::new(Router::new().route("/", post(handler)))
+            .post("/")
+            .multipart(Form::new().text("data", "x".repeat(513)))
+            .send()
+            .await
+            .unwrap();
+    }
+}
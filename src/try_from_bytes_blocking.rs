@@ -0,0 +1,130 @@
+use crate::try_from_chunks::capacity_hint;
+use crate::{FieldMetadata, TryFromAsyncRead, TypedMultipartError};
+use axum::body::Bytes;
+use tokio::io::{AsyncBufRead, AsyncReadExt};
+
+/// Types that are created by a synchronous, CPU-heavy parser, such as image or document decoders.
+///
+/// The field contents are buffered in memory, within the field size limit, and the parser is
+/// then run on a thread dedicated to blocking operations using [tokio::task::spawn_blocking], so
+/// the async runtime is not stalled. Every type implementing this trait automatically implements
+/// [TryFromAsyncRead] and therefore [TryFromChunks](crate::TryFromChunks).
+///
+/// A panic inside the parser is reported as an [Other](TypedMultipartError::Other) error.
+///
+/// ## Example
+///
+/// ```rust
+/// use axum::body::Bytes;
+/// use axum_typed_multipart::{FieldMetadata, TryFromBytesBlocking, TypedMultipartError};
+///
+/// /// Number of lines in a large text file.
+/// struct LineCount(usize);
+///
+/// impl TryFromBytesBlocking for LineCount {
+///     fn try_from_bytes_blocking(
+///         bytes: Bytes,
+///         _: FieldMetadata,
+///     ) -> Result<Self, TypedMultipartError> {
+///         Ok(LineCount(bytes.split(|byte| *byte == b'\n').count()))
+///     }
+/// }
+/// ```
+pub trait TryFromBytesBlocking: Sized + Send + 'static {
+    /// Create the type from the complete contents of the field.
+    ///
+    /// The `metadata` parameter contains information about the field.
+    fn try_from_bytes_blocking(
+        bytes: Bytes,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError>;
+}
+
+impl<T> TryFromAsyncRead for T
+where
+    T: TryFromBytesBlocking,
+{
+    async fn try_from_async_read(
+        mut reader: impl AsyncBufRead + Send + Unpin,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        let mut buf = Vec::with_capacity(capacity_hint(&metadata));
+        reader.read_to_end(&mut buf).await?;
+
+        let bytes = Bytes::from(buf);
+        let task = tokio::task::spawn_blocking(move || T::try_from_bytes_blocking(bytes, metadata));
+
+        task.await.map_err(|err| match err.try_into_panic() {
+            Ok(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown panic");
+                anyhow::anyhow!("field parser panicked: {message}")
+            }
+            Err(err) => anyhow::Error::new(err),
+        })?
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+    use crate::TryFromChunks;
+    use futures_util::stream;
+
+    #[derive(Debug)]
+    struct Data(usize);
+
+    impl TryFromBytesBlocking for Data {
+        fn try_from_bytes_blocking(
+            bytes: Bytes,
+            metadata: FieldMetadata,
+        ) -> Result<Self, TypedMultipartError> {
+            match &*bytes {
+                b"panic" => panic!("invalid data"),
+                b"" => Err(TypedMultipartError::WrongFieldType {
+                    field_name: metadata.name.unwrap_or_default(),
+                    wanted_type: "Data".into(),
+                    source: anyhow::anyhow!("empty data"),
+                }),
+                _ => Ok(Data(bytes.len())),
+            }
+        }
+    }
+
+    async fn parse(chunks: &[&'static str]) -> Result<Data, TypedMultipartError> {
+        let chunks =
+            stream::iter(chunks.iter().map(|chunk| Ok(Bytes::from_static(chunk.as_bytes()))));
+        Data::try_from_chunks(chunks, FieldMetadata::default()).await
+    }
+
+    #[tokio::test]
+    async fn test_try_from_bytes_blocking() {
+        assert_eq!(parse(&["Hello, ", "world!"]).await.unwrap().0, 13);
+    }
+
+    #[tokio::test]
+    async fn test_try_from_bytes_blocking_error() {
+        let res = parse(&[]).await;
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_try_from_bytes_blocking_panic() {
+        let res = parse(&["panic"]).await;
+        assert_eq!(res.unwrap_err().to_string(), "field parser panicked: invalid data");
+    }
+
+    #[tokio::test]
+    async fn test_try_from_bytes_blocking_too_large() {
+        let chunks = stream::iter([Err(TypedMultipartError::FieldTooLarge {
+            field_name: "data".into(),
+            limit_bytes: 1,
+        })]);
+        let res = Data::try_from_chunks(chunks, FieldMetadata::default()).await;
+        assert!(matches!(res, Err(TypedMultipartError::FieldTooLarge { .. })));
+    }
+}
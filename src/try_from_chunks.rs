@@ -1,11 +1,17 @@
 use crate::{util, FieldMetadata, TypedMultipartError};
-use async_trait::async_trait;
 use axum::body::Bytes;
+use axum::http::header::CONTENT_LENGTH;
 use bytes::BytesMut;
 use futures_core::stream::Stream;
 use futures_util::stream::StreamExt;
 use std::any::type_name;
+use std::future::Future;
+use std::pin::pin;
 use std::str::FromStr;
+use std::sync::Arc;
+
+/// Number of bytes reserved for a field received in multiple chunks when its size is unknown.
+const INITIAL_CAPACITY: usize = 8 * 1024;
 
 /// Types that can be created from a [Stream] of [Bytes].
 ///
@@ -17,15 +23,14 @@ use std::str::FromStr;
 ///
 /// ```rust
 /// use axum::body::Bytes;
-/// use axum_typed_multipart::{async_trait, FieldMetadata, TryFromChunks, TypedMultipartError};
+/// use axum_typed_multipart::{FieldMetadata, TryFromChunks, TypedMultipartError};
 /// use futures_util::stream::Stream;
 ///
 /// struct Data(String);
 ///
-/// #[async_trait]
 /// impl TryFromChunks for Data {
 ///     async fn try_from_chunks(
-///         chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+///         chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
 ///         metadata: FieldMetadata,
 ///     ) -> Result<Self, TypedMultipartError> {
 ///         let string = String::try_from_chunks(chunks, metadata).await?;
@@ -33,24 +38,44 @@ use std::str::FromStr;
 ///     }
 /// }
 /// ```
-#[async_trait]
 pub trait TryFromChunks: Sized {
     /// Consume the input [Stream] of [Bytes] to create the supplied type.
     ///
-    /// The `metadata` parameter contains information about the field.
-    async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+    /// The `metadata` parameter contains information about the field. The stream is not required
+    /// to be [Unpin], use [pin!] before polling it directly.
+    fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
-    ) -> Result<Self, TypedMultipartError>;
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+
+    /// Handle an error raised for the field before its contents are handed to
+    /// [TryFromChunks::try_from_chunks], e.g. because its `Content-Type` is not allowed or its
+    /// textual representation could not be read. The error is propagated by default.
+    #[doc(hidden)]
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        Err(err)
+    }
 }
 
-#[async_trait]
+/// Fields received as a single chunk are returned without copying their contents.
 impl TryFromChunks for Bytes {
     async fn try_from_chunks(
-        mut chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
-        _: FieldMetadata,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
-        let mut bytes = BytesMut::new();
+        let mut chunks = pin!(chunks);
+
+        let Some(first) = chunks.next().await.transpose()? else {
+            return Ok(Bytes::new());
+        };
+        let Some(second) = chunks.next().await.transpose()? else {
+            return Ok(first);
+        };
+
+        let received = first.len() + second.len();
+        let mut bytes = BytesMut::with_capacity(capacity_hint(&metadata).max(received));
+        bytes.extend_from_slice(&first);
+        bytes.extend_from_slice(&second);
 
         while let Some(chunk) = chunks.next().await {
             let chunk = chunk?;
@@ -61,10 +86,9 @@ impl TryFromChunks for Bytes {
     }
 }
 
-#[async_trait]
 impl TryFromChunks for String {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
@@ -78,16 +102,16 @@ impl TryFromChunks for String {
     }
 }
 
-#[async_trait]
 impl TryFromChunks for bool {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
-        let str = String::try_from_chunks(chunks, metadata).await?;
+        let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
+        let text = str_from_utf8::<bool>(&bytes, &field_name)?;
 
-        util::str_to_bool(str).ok_or(TypedMultipartError::WrongFieldType {
+        util::str_to_bool(text).ok_or(TypedMultipartError::WrongFieldType {
             field_name,
             wanted_type: type_name::<bool>().to_string(),
             source: anyhow::anyhow!("Invalid boolean value"),
@@ -95,20 +119,80 @@ impl TryFromChunks for bool {
     }
 }
 
+/// Raw binary contents of the field.
+///
+/// When used as a struct field `Vec<u8>` is treated as a byte payload and not as a list of [u8]
+/// fields, use a different [FieldCollector](crate::FieldCollector) like
+/// [VecDeque](std::collections::VecDeque) for the latter.
+impl TryFromChunks for Vec<u8> {
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        Bytes::try_from_chunks(chunks, metadata).await.map(Vec::from)
+    }
+}
+
+impl TryFromChunks for Box<[u8]> {
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        Vec::<u8>::try_from_chunks(chunks, metadata).await.map(Vec::into_boxed_slice)
+    }
+}
+
+impl TryFromChunks for Arc<[u8]> {
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        Vec::<u8>::try_from_chunks(chunks, metadata).await.map(Arc::from)
+    }
+}
+
+/// Captures the errors raised while parsing the field instead of failing the whole request.
+///
+/// Errors caused by a malformed request body
+/// ([InvalidRequestBody](TypedMultipartError::InvalidRequestBody)) are still propagated, since
+/// the remaining fields cannot be read.
+impl<T, E> TryFromChunks for Result<T, E>
+where
+    T: TryFromChunks + Send,
+    E: From<TypedMultipartError> + Send,
+{
+    async fn try_from_chunks(
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
+        metadata: FieldMetadata,
+    ) -> Result<Self, TypedMultipartError> {
+        match T::try_from_chunks(chunks, metadata).await {
+            Ok(value) => Ok(Ok(value)),
+            Err(err) => Self::from_field_error(err),
+        }
+    }
+
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        match err {
+            TypedMultipartError::InvalidRequestBody { .. } => Err(err),
+            err => Ok(Err(err.into())),
+        }
+    }
+}
+
 /// Generate a [TryFromChunks] implementation for the supplied data type using
 /// the `str::parse` method on the textual representation of the field data.
 macro_rules! gen_try_from_chunks_impl {
     ( $type: ty ) => {
-        #[async_trait]
         impl TryFromChunks for $type {
             async fn try_from_chunks(
-                chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync+ Unpin,
+                chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
                 metadata: FieldMetadata,
             ) -> Result<Self, TypedMultipartError> {
                 let field_name = get_field_name(&metadata.name);
-                let text = String::try_from_chunks(chunks, metadata).await?;
+                let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
+                let text = str_from_utf8::<$type>(&bytes, &field_name)?;
 
-                str::parse(&text).map_err(|err| TypedMultipartError::WrongFieldType {
+                str::parse(text).map_err(|err| TypedMultipartError::WrongFieldType {
                     field_name,
                     wanted_type: type_name::<$type>().to_string(),
                     source: anyhow::Error::new(err),
@@ -135,13 +219,13 @@ gen_try_from_chunks_impl!(f64);
 gen_try_from_chunks_impl!(char);
 
 #[cfg(feature = "tempfile_3")]
-#[async_trait]
 impl TryFromChunks for tempfile_3::NamedTempFile {
     async fn try_from_chunks(
-        mut chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         _: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         use tokio::io::AsyncWriteExt as _;
+        let mut chunks = pin!(chunks);
         let temp_file = tempfile_3::NamedTempFile::new().map_err(anyhow::Error::new)?;
         let std_file = temp_file.reopen().map_err(anyhow::Error::new)?;
         let mut async_file = tokio::fs::File::from_std(std_file);
@@ -158,10 +242,9 @@ impl TryFromChunks for tempfile_3::NamedTempFile {
 }
 
 #[cfg(feature = "uuid_1")]
-#[async_trait]
 impl TryFromChunks for uuid_1::Uuid {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
@@ -175,7 +258,6 @@ impl TryFromChunks for uuid_1::Uuid {
 }
 
 #[cfg(feature = "chrono_0_4")]
-#[async_trait]
 impl<Tz, Err> TryFromChunks for chrono_0_4::DateTime<Tz>
 where
     Err: Into<anyhow::Error>,
@@ -183,17 +265,12 @@ where
     chrono_0_4::DateTime<Tz>: FromStr<Err = Err>,
 {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
         let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
-        let body_str =
-            std::str::from_utf8(&bytes).map_err(|err| TypedMultipartError::WrongFieldType {
-                field_name: field_name.clone(),
-                wanted_type: type_name::<chrono_0_4::DateTime<Tz>>().to_string(),
-                source: err.into(),
-            })?;
+        let body_str = str_from_utf8::<chrono_0_4::DateTime<Tz>>(&bytes, &field_name)?;
         chrono_0_4::DateTime::<Tz>::from_str(body_str).map_err(|err| {
             TypedMultipartError::WrongFieldType {
                 field_name,
@@ -204,24 +281,18 @@ where
     }
 }
 #[cfg(feature = "chrono_0_4")]
-#[async_trait]
 impl<Err> TryFromChunks for chrono_0_4::NaiveDate
 where
     Err: Into<anyhow::Error>,
     chrono_0_4::NaiveDate: FromStr<Err = Err>,
 {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
         let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
-        let body_str =
-            std::str::from_utf8(&bytes).map_err(|err| TypedMultipartError::WrongFieldType {
-                field_name: field_name.clone(),
-                wanted_type: type_name::<chrono_0_4::NaiveDate>().to_string(),
-                source: err.into(),
-            })?;
+        let body_str = str_from_utf8::<chrono_0_4::NaiveDate>(&bytes, &field_name)?;
         chrono_0_4::NaiveDate::from_str(body_str).map_err(|err| {
             TypedMultipartError::WrongFieldType {
                 field_name,
@@ -233,24 +304,18 @@ where
 }
 
 #[cfg(feature = "rust_decimal_1")]
-#[async_trait]
 impl<Err> TryFromChunks for rust_decimal_1::Decimal
 where
     Err: Into<anyhow::Error>,
     rust_decimal_1::Decimal: FromStr<Err = Err>,
 {
     async fn try_from_chunks(
-        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+        chunks: impl Stream<Item = Result<Bytes, TypedMultipartError>> + Send,
         metadata: FieldMetadata,
     ) -> Result<Self, TypedMultipartError> {
         let field_name = get_field_name(&metadata.name);
         let bytes = Bytes::try_from_chunks(chunks, metadata).await?;
-        let body_str =
-            std::str::from_utf8(&bytes).map_err(|err| TypedMultipartError::WrongFieldType {
-                field_name: field_name.clone(),
-                wanted_type: type_name::<rust_decimal_1::Decimal>().to_string(),
-                source: err.into(),
-            })?;
+        let body_str = str_from_utf8::<rust_decimal_1::Decimal>(&bytes, &field_name)?;
         rust_decimal_1::Decimal::from_str(body_str).map_err(|err| {
             TypedMultipartError::WrongFieldType {
                 field_name,
@@ -261,6 +326,37 @@ where
     }
 }
 
+/// Number of bytes to reserve for a field received in multiple chunks.
+///
+/// The part `Content-Length` is sent by the client, so it is only trusted up to the field limit,
+/// or up to [INITIAL_CAPACITY] for unlimited fields. Browsers do not send it, in which case a
+/// small buffer is reserved and left to grow, instead of reserving the whole limit up front.
+pub(crate) fn capacity_hint(metadata: &FieldMetadata) -> usize {
+    let content_length = metadata
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+
+    match (content_length, metadata.limit_bytes) {
+        (Some(content_length), Some(limit_bytes)) => content_length.min(limit_bytes),
+        (Some(size_bytes), None) | (None, Some(size_bytes)) => size_bytes.min(INITIAL_CAPACITY),
+        (None, None) => INITIAL_CAPACITY,
+    }
+}
+
+/// Borrow the textual representation of the field, reporting invalid UTF-8 as a wrong type.
+fn str_from_utf8<'a, T>(
+    bytes: &'a Bytes,
+    field_name: &str,
+) -> Result<&'a str, TypedMultipartError> {
+    std::str::from_utf8(bytes).map_err(|err| TypedMultipartError::WrongFieldType {
+        field_name: field_name.to_owned(),
+        wanted_type: type_name::<T>().to_string(),
+        source: err.into(),
+    })
+}
+
 fn get_field_name(name: &Option<String>) -> String {
     // Name should always be present, but handle the edge case.
     name.clone().unwrap_or("<unknown>".into())
@@ -313,6 +409,65 @@ mod tests {
         test_try_from_chunks_valid::<Bytes>("asd", "asd").await;
     }
 
+    #[tokio::test]
+    async fn test_try_from_chunks_bytes_single_chunk() {
+        let chunk = Bytes::from_static(b"asd");
+        let chunks = stream::iter([Ok(chunk.clone())]);
+        let res = Bytes::try_from_chunks(chunks, FieldMetadata::default()).await.unwrap();
+        assert_eq!(res.as_ptr(), chunk.as_ptr());
+
+        let res = Bytes::try_from_chunks(stream::empty(), FieldMetadata::default()).await.unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_hint() {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, "100".parse().unwrap());
+        let sized = FieldMetadata { headers: Arc::new(headers), ..Default::default() };
+
+        assert_eq!(capacity_hint(&sized), 100);
+        assert_eq!(capacity_hint(&FieldMetadata { limit_bytes: Some(200), ..sized.clone() }), 100);
+        assert_eq!(capacity_hint(&FieldMetadata { limit_bytes: Some(50), ..sized }), 50);
+
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, "1000000000".parse().unwrap());
+        let oversized = FieldMetadata { headers: Arc::new(headers), ..Default::default() };
+        assert_eq!(capacity_hint(&oversized), INITIAL_CAPACITY);
+
+        let unsized_field = FieldMetadata { limit_bytes: Some(20), ..Default::default() };
+        assert_eq!(capacity_hint(&unsized_field), 20);
+        let unsized_field = FieldMetadata { limit_bytes: Some(100 << 20), ..Default::default() };
+        assert_eq!(capacity_hint(&unsized_field), INITIAL_CAPACITY);
+        assert_eq!(capacity_hint(&FieldMetadata::default()), INITIAL_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn test_try_from_chunks_byte_slices() {
+        test_try_from_chunks_valid::<Vec<u8>>(vec![0x80, 0x81], vec![0x80, 0x81]).await;
+        test_try_from_chunks_valid::<Box<[u8]>>("asd", b"asd".as_slice()).await;
+        test_try_from_chunks_valid::<Arc<[u8]>>("asd", b"asd".as_slice()).await;
+    }
+
+    #[tokio::test]
+    async fn test_try_from_chunks_result() {
+        type Data = Result<u8, TypedMultipartError>;
+        let metadata = FieldMetadata { name: Some("test".into()), ..Default::default() };
+
+        let res = Data::try_from_chunks(create_chunks("42"), metadata.clone()).await.unwrap();
+        assert_eq!(res.unwrap(), 42);
+
+        let res = Data::try_from_chunks(create_chunks("invalid"), metadata.clone()).await.unwrap();
+        assert!(matches!(res, Err(TypedMultipartError::WrongFieldType { .. })));
+
+        let chunks = stream::iter([Err(TypedMultipartError::FieldTooLarge {
+            field_name: "test".into(),
+            limit_bytes: 1,
+        })]);
+        let res = Data::try_from_chunks(chunks, metadata).await.unwrap();
+        assert!(matches!(res, Err(TypedMultipartError::FieldTooLarge { .. })));
+    }
+
     #[tokio::test]
     async fn test_try_from_chunks_string() {
         test_try_from_chunks_valid::<String>("asd", "asd").await;
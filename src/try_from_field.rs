@@ -1,10 +1,11 @@
+use crate::form_outcome::RecordedField;
 use crate::try_from_chunks::TryFromChunks;
 use crate::{FieldMetadata, TypedMultipartError};
-use async_trait::async_trait;
 use axum::extract::multipart::Field;
-use futures_util::stream::StreamExt;
-use futures_util::TryStreamExt;
-use std::mem;
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 
 /// Types that can be created from a multipart field.
 ///
@@ -12,13 +13,30 @@ use std::mem;
 ///
 /// **Note:** Prefer implementing [TryFromChunks] instead, which automatically provides
 /// this implementation with proper size limit handling.
-#[async_trait]
 pub trait TryFromField: Sized {
     /// Creates an instance from a multipart field with optional size limit.
-    async fn try_from_field(
+    fn try_from_field(
+        field: Field<'_>,
+        limit_bytes: Option<usize>,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+
+    /// Same as [TryFromField::try_from_field], reusing the metadata already extracted from the
+    /// field by the caller instead of building it again.
+    #[doc(hidden)]
+    fn try_from_field_with_metadata(
         field: Field<'_>,
+        _metadata: FieldMetadata,
         limit_bytes: Option<usize>,
-    ) -> Result<Self, TypedMultipartError>;
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        Self::try_from_field(field, limit_bytes)
+    }
+
+    /// Handle an error raised for the field before it is parsed, see
+    /// [TryFromChunks::from_field_error].
+    #[doc(hidden)]
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        Err(err)
+    }
 }
 
 /// Stateful variant of [TryFromField] that provides access to application state during parsing.
@@ -28,61 +46,186 @@ pub trait TryFromField: Sized {
 /// ```rust,no_run
 #[doc = include_str!("../examples/state.rs")]
 /// ```
-#[async_trait]
 pub trait TryFromFieldWithState<S>: Sized {
     /// Creates an instance from a field with access to application state.
-    async fn try_from_field_with_state(
+    fn try_from_field_with_state(
         field: Field<'_>,
         limit_bytes: Option<usize>,
         state: &S,
-    ) -> Result<Self, TypedMultipartError>;
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+
+    /// Same as [TryFromFieldWithState::try_from_field_with_state], reusing the metadata already
+    /// extracted from the field by the caller instead of building it again.
+    #[doc(hidden)]
+    fn try_from_field_with_state_and_metadata(
+        field: Field<'_>,
+        _metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+        state: &S,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        Self::try_from_field_with_state(field, limit_bytes, state)
+    }
+
+    /// Handle an error raised for the field before it is parsed, see
+    /// [TryFromChunks::from_field_error].
+    #[doc(hidden)]
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        Err(err)
+    }
 }
 
-#[async_trait]
 impl<T, S> TryFromFieldWithState<S> for T
 where
     T: TryFromField,
 {
-    async fn try_from_field_with_state(
+    fn try_from_field_with_state(
         field: Field<'_>,
         limit_bytes: Option<usize>,
         _state: &S,
-    ) -> Result<Self, TypedMultipartError> {
-        T::try_from_field(field, limit_bytes).await
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        // Not an `async fn`, so that the future does not hold `state` and `S` is not required to
+        // be `Sync`.
+        T::try_from_field(field, limit_bytes)
+    }
+
+    fn try_from_field_with_state_and_metadata(
+        field: Field<'_>,
+        metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+        _state: &S,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        T::try_from_field_with_metadata(field, metadata, limit_bytes)
+    }
+
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        <T as TryFromField>::from_field_error(err)
     }
 }
 
-#[async_trait]
 impl<T> TryFromField for T
 where
-    T: TryFromChunks + Send + Sync,
+    T: TryFromChunks,
 {
     async fn try_from_field(
         field: Field<'_>,
         limit_bytes: Option<usize>,
     ) -> Result<Self, TypedMultipartError> {
         let metadata = FieldMetadata::from(&field);
-        let mut field_name = metadata.name.clone().unwrap_or(String::new());
-        let mut size_bytes = 0;
-
-        let chunks = field.map_err(TypedMultipartError::from).map(|chunk| {
-            if let Ok(chunk) = chunk.as_ref() {
-                size_bytes += chunk.len();
-
-                if let Some(limit_bytes) = limit_bytes {
-                    if size_bytes > limit_bytes {
-                        return Err(TypedMultipartError::FieldTooLarge {
-                            field_name: mem::take(&mut field_name),
-                            limit_bytes,
-                        });
-                    }
+        <Self as TryFromField>::try_from_field_with_metadata(field, metadata, limit_bytes).await
+    }
+
+    async fn try_from_field_with_metadata(
+        field: Field<'_>,
+        metadata: FieldMetadata,
+        limit_bytes: Option<usize>,
+    ) -> Result<Self, TypedMultipartError> {
+        let chunks = LimitedField {
+            #[cfg(feature = "infer_0_19")]
+            sniffer: metadata.sniffed_content_type.clone().map(crate::sniff::Sniffer::new),
+            recording: metadata.recorder.as_ref().map(|recorder| recorder.field()),
+            field,
+            limit_bytes,
+            size_bytes: 0,
+        };
+        T::try_from_chunks(chunks, FieldMetadata { limit_bytes, ..metadata }).await
+    }
+
+    fn from_field_error(err: TypedMultipartError) -> Result<Self, TypedMultipartError> {
+        <T as TryFromChunks>::from_field_error(err)
+    }
+}
+
+/// Stream over the chunks of a field, failing once the size limit is exceeded.
+///
+/// The field name is only copied when the error is raised, since it can be read from the field.
+/// Fields declared with `#[form_data(sniff)]` also have their content type detected and checked
+/// before the first bytes are passed on, and the contents of textual fields are recorded while
+/// a [FormOutcome](crate::FormOutcome) is extracted.
+struct LimitedField<'a> {
+    field: Field<'a>,
+    limit_bytes: Option<usize>,
+    size_bytes: usize,
+    recording: Option<RecordedField>,
+    #[cfg(feature = "infer_0_19")]
+    sniffer: Option<crate::sniff::Sniffer>,
+}
+
+impl LimitedField<'_> {
+    /// Poll the next chunk of the field, checking the size limit and recording it.
+    fn poll_chunk(
+        field: &mut Field<'_>,
+        limit_bytes: Option<usize>,
+        size_bytes: &mut usize,
+        recording: &mut Option<RecordedField>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<bytes::Bytes, TypedMultipartError>>> {
+        let chunk = match ready!(Pin::new(&mut *field).poll_next(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => {
+                if let Some(recording) = recording {
+                    recording.finish(field.name().unwrap_or_default());
                 }
+                return Poll::Ready(None);
+            }
+        };
+
+        if let Some(recording) = recording {
+            recording.push(&chunk);
+        }
+
+        *size_bytes += chunk.len();
+        if let Some(limit_bytes) = limit_bytes {
+            if *size_bytes > limit_bytes {
+                let field_name = field.name().unwrap_or_default().to_owned();
+                return Poll::Ready(Some(Err(TypedMultipartError::FieldTooLarge {
+                    field_name,
+                    limit_bytes,
+                })));
             }
+        }
+
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
 
-            chunk
-        });
+impl Stream for LimitedField<'_> {
+    type Item = Result<bytes::Bytes, TypedMultipartError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        #[cfg(feature = "infer_0_19")]
+        if let Some(sniffer) = &mut this.sniffer {
+            // Chunks are held back until enough bytes are collected, so the field is read until
+            // the sniffer releases them or no more data is available yet.
+            loop {
+                let poll = Self::poll_chunk(
+                    &mut this.field,
+                    this.limit_bytes,
+                    &mut this.size_bytes,
+                    &mut this.recording,
+                    cx,
+                );
+                match ready!(poll) {
+                    Some(Ok(chunk)) => {
+                        if let Some(res) = sniffer.push(&this.field, chunk) {
+                            return Poll::Ready(Some(res));
+                        }
+                    }
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                    None => return Poll::Ready(sniffer.finish(&this.field)),
+                }
+            }
+        }
 
-        T::try_from_chunks(chunks, metadata).await
+        Self::poll_chunk(
+            &mut this.field,
+            this.limit_bytes,
+            &mut this.size_bytes,
+            &mut this.recording,
+            cx,
+        )
     }
 }
 
@@ -101,10 +244,9 @@ mod tests {
     #[derive(Debug)]
     struct Data(String);
 
-    #[async_trait]
     impl TryFromChunks for Data {
         async fn try_from_chunks(
-            chunks: impl Stream<Item = Result<bytes::Bytes, TypedMultipartError>> + Send + Sync + Unpin,
+            chunks: impl Stream<Item = Result<bytes::Bytes, TypedMultipartError>> + Send,
             metadata: FieldMetadata,
         ) -> Result<Self, TypedMultipartError> {
             let data = String::try_from_chunks(chunks, metadata).await?;
@@ -163,7 +305,6 @@ mod tests_with_state {
 
     struct DataWithState(String);
 
-    #[async_trait]
     impl TryFromFieldWithState<State> for DataWithState {
         async fn try_from_field_with_state(
             field: Field<'_>,
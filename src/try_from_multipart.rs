@@ -1,6 +1,7 @@
+use crate::__private::FieldErrors;
 use crate::TypedMultipartError;
-use async_trait::async_trait;
 use axum::extract::Multipart;
+use std::future::Future;
 
 /// Types that can be created from multipart form data.
 ///
@@ -17,9 +18,10 @@ use axum::extract::Multipart;
 ///     name: String,
 /// }
 /// ```
-#[async_trait]
 pub trait TryFromMultipart: Sized {
-    async fn try_from_multipart(multipart: &mut Multipart) -> Result<Self, TypedMultipartError>;
+    fn try_from_multipart(
+        multipart: &mut Multipart,
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
 }
 
 /// Stateful variant of [TryFromMultipart] that provides access to application state during parsing.
@@ -31,25 +33,39 @@ pub trait TryFromMultipart: Sized {
 /// ```rust,no_run
 #[doc = include_str!("../examples/state.rs")]
 /// ```
-#[async_trait]
 pub trait TryFromMultipartWithState<S>: Sized {
     /// Creates an instance from multipart data with access to application state.
-    async fn try_from_multipart_with_state(
+    fn try_from_multipart_with_state(
         multipart: &mut Multipart,
         state: &S,
-    ) -> Result<Self, TypedMultipartError>;
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send;
+
+    /// Like [TryFromMultipartWithState::try_from_multipart_with_state], but storing the errors
+    /// related to a specific field in `errors` instead of returning them, so that the remaining
+    /// fields can still be checked. Returns [None] if any error was stored.
+    ///
+    /// Used by [FormOutcome](crate::FormOutcome), the default implementation stores the single
+    /// error returned by [TryFromMultipartWithState::try_from_multipart_with_state].
+    #[doc(hidden)]
+    fn try_from_multipart_with_state_and_errors(
+        multipart: &mut Multipart,
+        state: &S,
+        errors: &mut FieldErrors,
+    ) -> impl Future<Output = Result<Option<Self>, TypedMultipartError>> + Send {
+        let res = Self::try_from_multipart_with_state(multipart, state);
+        async move { errors.check(res.await) }
+    }
 }
 
-#[async_trait]
 impl<T, S> TryFromMultipartWithState<S> for T
 where
     T: TryFromMultipart,
 {
-    async fn try_from_multipart_with_state(
+    fn try_from_multipart_with_state(
         multipart: &mut Multipart,
         _state: &S,
-    ) -> Result<Self, TypedMultipartError> {
-        T::try_from_multipart(multipart).await
+    ) -> impl Future<Output = Result<Self, TypedMultipartError>> + Send {
+        T::try_from_multipart(multipart)
     }
 }
 
@@ -65,7 +81,6 @@ mod tests {
         name: String,
     }
 
-    #[async_trait]
     impl TryFromMultipart for Data {
         async fn try_from_multipart(
             multipart: &mut Multipart,
@@ -69,7 +69,6 @@ mod tests {
 
     struct Data(String);
 
-    #[async_trait::async_trait]
     impl TryFromMultipart for Data {
         async fn try_from_multipart(_: &mut Multipart) -> Result<Self, TypedMultipartError> {
             Ok(Self(String::from("data")))
@@ -1,6 +1,7 @@
 use axum::extract::multipart::{MultipartError, MultipartRejection};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use std::io;
 
 /// Error type for multipart parsing operations.
 #[non_exhaustive]
@@ -39,6 +40,15 @@ pub enum TypedMultipartError {
     #[error("field '{field_name}' is larger than {limit_bytes} bytes")]
     FieldTooLarge { field_name: String, limit_bytes: usize },
 
+    #[error("field '{field_name}' must be present {expected} times, found {actual}")]
+    WrongFieldCount { field_name: String, expected: usize, actual: usize },
+
+    #[error("field '{field_name}' has unsupported content type '{content_type}'")]
+    InvalidContentType { field_name: String, content_type: String },
+
+    #[error("request data is invalid: {source}")]
+    InvalidData { source: anyhow::Error },
+
     #[error(transparent)]
     Other {
         #[from]
@@ -54,13 +64,59 @@ impl TypedMultipartError {
             | Self::DuplicateField { .. }
             | Self::UnknownField { .. }
             | Self::InvalidEnumValue { .. }
-            | Self::NamelessField { .. } => StatusCode::BAD_REQUEST,
+            | Self::NamelessField { .. }
+            | Self::WrongFieldCount { .. }
+            | Self::InvalidData { .. } => StatusCode::BAD_REQUEST,
             | Self::FieldTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            | Self::InvalidContentType { .. } => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             | Self::InvalidRequest { source } => source.status(),
             | Self::InvalidRequestBody { source } => source.status(),
             | Self::Other { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
+
+    /// Name of the field that caused the error, if the error is related to a specific field.
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            | Self::MissingField { field_name }
+            | Self::WrongFieldType { field_name, .. }
+            | Self::DuplicateField { field_name }
+            | Self::UnknownField { field_name }
+            | Self::InvalidEnumValue { field_name, .. }
+            | Self::FieldTooLarge { field_name, .. }
+            | Self::WrongFieldCount { field_name, .. }
+            | Self::InvalidContentType { field_name, .. } => Some(field_name),
+            | Self::InvalidRequest { .. }
+            | Self::InvalidRequestBody { .. }
+            | Self::NamelessField
+            | Self::InvalidData { .. }
+            | Self::Other { .. } => None,
+        }
+    }
+}
+
+/// Converts the errors raised by readers over the field contents.
+///
+/// If the error was originally raised while receiving the field it is returned as is. Errors
+/// caused by the contents sent by the client, such as invalid UTF-8 or truncated data, are
+/// wrapped in [InvalidData](TypedMultipartError::InvalidData), the others in
+/// [Other](TypedMultipartError::Other).
+impl From<io::Error> for TypedMultipartError {
+    fn from(err: io::Error) -> Self {
+        if !err.get_ref().is_some_and(|inner| inner.is::<Self>()) {
+            return match err.kind() {
+                io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => {
+                    Self::InvalidData { source: err.into() }
+                }
+                _ => anyhow::Error::new(err).into(),
+            };
+        }
+
+        match err.into_inner().map(|inner| inner.downcast::<Self>()) {
+            Some(Ok(inner)) => *inner,
+            _ => unreachable!("inner error is a TypedMultipartError"),
+        }
+    }
 }
 
 impl IntoResponse for TypedMultipartError {
@@ -128,6 +184,7 @@ mod tests {
         let field_name = "data".to_string();
         let error = TypedMultipartError::MissingField { field_name };
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("data"));
         assert_eq!(error.to_string(), "field 'data' is required");
     }
 
@@ -138,6 +195,7 @@ mod tests {
         let source = anyhow::anyhow!("invalid type");
         let error = TypedMultipartError::WrongFieldType { field_name, wanted_type, source };
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("data"));
         assert_eq!(error.to_string(), "field 'data' must be of type 'bar': invalid type");
     }
 
@@ -146,6 +204,7 @@ mod tests {
         let field_name = "data".to_string();
         let error = TypedMultipartError::DuplicateField { field_name };
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("data"));
         assert_eq!(error.to_string(), "field 'data' is already present");
     }
 
@@ -154,6 +213,7 @@ mod tests {
         let field_name = "data".to_string();
         let error = TypedMultipartError::UnknownField { field_name };
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("data"));
         assert_eq!(error.to_string(), "field 'data' is not expected");
     }
 
@@ -163,6 +223,7 @@ mod tests {
         let value = "invalid".to_string();
         let error = TypedMultipartError::InvalidEnumValue { field_name, value };
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("status"));
         assert_eq!(error.to_string(), "'invalid' is not a valid value for field 'status'");
     }
 
@@ -170,6 +231,7 @@ mod tests {
     async fn test_nameless_field() {
         let error = TypedMultipartError::NamelessField;
         assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), None);
         assert_eq!(error.to_string(), "field name is empty");
     }
 
@@ -179,14 +241,61 @@ mod tests {
         let limit_bytes = 42;
         let error = TypedMultipartError::FieldTooLarge { field_name, limit_bytes };
         assert_eq!(error.get_status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error.field_name(), Some("data"));
         assert_eq!(error.to_string(), "field 'data' is larger than 42 bytes");
     }
 
+    #[tokio::test]
+    async fn test_wrong_field_count() {
+        let field_name = "data".to_string();
+        let error = TypedMultipartError::WrongFieldCount { field_name, expected: 3, actual: 2 };
+        assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), Some("data"));
+        assert_eq!(error.to_string(), "field 'data' must be present 3 times, found 2");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_content_type() {
+        let field_name = "data".to_string();
+        let content_type = "text/plain".to_string();
+        let error = TypedMultipartError::InvalidContentType { field_name, content_type };
+        assert_eq!(error.get_status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(error.field_name(), Some("data"));
+        assert_eq!(error.to_string(), "field 'data' has unsupported content type 'text/plain'");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_data() {
+        let source = anyhow::anyhow!("start is after end");
+        let error = TypedMultipartError::InvalidData { source };
+        assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.field_name(), None);
+        assert_eq!(error.to_string(), "request data is invalid: start is after end");
+    }
+
     #[tokio::test]
     async fn test_other() {
         let source = anyhow::anyhow!("data");
         let error = TypedMultipartError::Other { source };
         assert_eq!(error.get_status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.field_name(), None);
         assert_eq!(error.to_string(), "data");
     }
+
+    #[test]
+    fn test_from_io_error() {
+        let field_name = "data".to_string();
+        let source = TypedMultipartError::FieldTooLarge { field_name, limit_bytes: 42 };
+        let error = TypedMultipartError::from(io::Error::other(source));
+        assert!(matches!(error, TypedMultipartError::FieldTooLarge { limit_bytes: 42, .. }));
+
+        for kind in [io::ErrorKind::InvalidData, io::ErrorKind::UnexpectedEof] {
+            let error = TypedMultipartError::from(io::Error::from(kind));
+            assert!(matches!(error, TypedMultipartError::InvalidData { .. }));
+            assert_eq!(error.get_status(), StatusCode::BAD_REQUEST);
+        }
+
+        let error = TypedMultipartError::from(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(error, TypedMultipartError::Other { .. }));
+    }
 }
@@ -14,3 +14,38 @@ pub fn str_to_bool(val: impl AsRef<str>) -> Option<bool> {
         None
     }
 }
+
+/// Returns the media type of the supplied `Content-Type`, without parameters and in lowercase.
+pub fn mime_essence(content_type: &str) -> String {
+    content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase()
+}
+
+/// Checks whether the supplied media type matches a pattern like `image/png`, `image/*` or
+/// `*/*`, ignoring parameters and case.
+pub fn content_type_matches(content_type: &str, pattern: &str) -> bool {
+    let essence = mime_essence(content_type);
+    let pattern = pattern.to_ascii_lowercase();
+
+    match pattern.strip_suffix("/*") {
+        Some("*") => true,
+        Some(kind) => essence.split_once('/').is_some_and(|(essence_kind, _)| essence_kind == kind),
+        None => essence == pattern,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_matches() {
+        assert!(content_type_matches("image/png", "image/png"));
+        assert!(content_type_matches("Image/PNG; name=avatar", "image/png"));
+        assert!(content_type_matches("image/png", "image/*"));
+        assert!(content_type_matches("text/plain", "*/*"));
+        assert!(!content_type_matches("image/png", "image/jpeg"));
+        assert!(!content_type_matches("imagex/png", "image/*"));
+        assert!(!content_type_matches("image", "image/*"));
+    }
+}
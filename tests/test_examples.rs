@@ -111,6 +111,24 @@ async fn test_upload_success() {
     assert_eq!(res.status(), StatusCode::CREATED);
 }
 
+#[tokio::test]
+async fn test_upload_path_traversal() {
+    let client = TestClient::new(upload::app());
+    let file_part = Part::bytes(b"test content".to_vec()).file_name("../../test.txt");
+    let form = Form::new().part("image", file_part).text("author", "../testuser");
+    let res = client.post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_upload_reserved_author() {
+    let client = TestClient::new(upload::app());
+    let file_part = Part::bytes(b"test content".to_vec()).file_name("test.txt");
+    let form = Form::new().part("image", file_part).text("author", "nul.");
+    let res = client.post("/").multipart(form).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_upload_missing_file() {
     let client = TestClient::new(upload::app());